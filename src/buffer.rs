@@ -0,0 +1,256 @@
+use core::fmt;
+use core::ops::Deref;
+use crate::simple::StackSimple;
+#[cfg(feature = "embedded-io")]
+use crate::error::CapacityError;
+
+// FOR THE BYTES // StackSimple<u8, C> as a bounded i/o buffer
+// writing appends as many bytes as fit. StackSimple<u8, C> reads from the front and shifts the rest down on every read, so a read is O(len).
+// StackByteBuffer keeps a read cursor instead and only shifts when the room of the consumed bytes is needed.
+// std::io::{Read, Write} behind the std feature, embedded_io::{Read, Write} behind the embedded-io feature.
+
+impl<const C: usize> StackSimple<u8, C> {
+    // appends as many of the bytes as fit, returns how many that was
+    pub fn extend_from_slice_partial(&mut self, bytes: &[u8]) -> usize {
+        let n = bytes.len().min(C - self.len());
+        self.extend_from_slice(&bytes[..n]).unwrap(); // unwrap bc n fits
+        n
+    }
+
+    // moves as many bytes from the front as fit into buf, returns how many that was.
+    // the rest shifts down, so every read is O(len). for streaming through the bytes use StackByteBuffer, it reads in O(n).
+    pub fn read_front(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.len());
+        buf[..n].copy_from_slice(&self[..n]);
+        self.drain(..n);
+        n
+    }
+}
+
+
+pub struct StackByteBuffer<const C: usize> {
+    bytes: StackSimple<u8, C>,
+    read_pos: usize, // the bytes before this were read already. their room is taken back when it is needed
+}
+
+impl<const C: usize> StackByteBuffer<C> {
+    pub const fn new() -> Self {
+        Self {
+            bytes: StackSimple::new(),
+            read_pos: 0,
+        }
+    }
+
+    // the bytes that were not read yet. also what Deref gives
+    pub fn unread(&self) -> &[u8] {
+        &self.bytes[self.read_pos..]
+    }
+    // the number of unread bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len() - self.read_pos
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+    // the room for writing, the room of the consumed bytes counts
+    pub fn remaining_capacity(&self) -> usize {
+        C - self.len()
+    }
+
+    // marks the first n unread bytes as read. panics if n > len.
+    pub fn consume(&mut self, n: usize) {
+        if n > self.len() {
+            panic!("consume count (is {}) should be <= len (is {})", n, self.len());
+        }
+        self.read_pos += n;
+        if self.read_pos == self.bytes.len() {
+            self.clear(); // all read, start at the front again for free
+        }
+    }
+
+    // moves the unread bytes to the front so the room of the consumed ones can be written to
+    pub fn discard_consumed(&mut self) {
+        if self.read_pos > 0 {
+            self.bytes.drain(..self.read_pos);
+            self.read_pos = 0;
+        }
+    }
+
+    // appends as many of the bytes as fit, returns how many that was. discards the consumed bytes first if that makes more fit.
+    // same name and result as on StackSimple<u8, C>
+    pub fn extend_from_slice_partial(&mut self, bytes: &[u8]) -> usize {
+        if C - self.bytes.len() < bytes.len() {
+            self.discard_consumed();
+        }
+        self.bytes.extend_from_slice_partial(bytes)
+    }
+
+    // copies as many unread bytes as fit into buf and consumes them, returns how many that was
+    pub fn read_into(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.len());
+        buf[..n].copy_from_slice(&self.unread()[..n]);
+        self.consume(n);
+        n
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.read_pos = 0;
+    }
+
+    // the unread bytes
+    pub fn into_inner(mut self) -> StackSimple<u8, C> {
+        self.discard_consumed();
+        self.bytes
+    }
+}
+
+impl<const C: usize> From<StackSimple<u8, C>> for StackByteBuffer<C> {
+    fn from(bytes: StackSimple<u8, C>) -> Self {
+        Self {
+            bytes,
+            read_pos: 0,
+        }
+    }
+}
+impl<const C: usize> Deref for StackByteBuffer<C> {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] {
+        self.unread()
+    }
+}
+impl<const C: usize> Default for StackByteBuffer<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const C: usize> Clone for StackByteBuffer<C> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            read_pos: self.read_pos,
+        }
+    }
+}
+impl<const C: usize> fmt::Debug for StackByteBuffer<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackByteBuffer")
+            .field("unread", &self.unread())
+            .field("capacity", &C)
+            .finish()
+    }
+}
+
+
+// std::io. a full buffer writes Ok(0), so write_all gives a WriteZero error. an empty buffer reads Ok(0).
+
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::Write for StackSimple<u8, C> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.extend_from_slice_partial(buf))
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+// every read shifts the unread bytes down, O(len) per read. StackByteBuffer reads without shifting.
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::Read for StackSimple<u8, C> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_front(buf))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::Write for StackByteBuffer<C> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.extend_from_slice_partial(buf))
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::Read for StackByteBuffer<C> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_into(buf))
+    }
+}
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::BufRead for StackByteBuffer<C> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(self.unread())
+    }
+    fn consume(&mut self, n: usize) {
+        StackByteBuffer::consume(self, n)
+    }
+}
+
+
+// embedded_io. a full buffer is a CapacityError with the WriteZero kind, as embedded_io wants instead of Ok(0).
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Error for CapacityError<()> {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::WriteZero
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::ErrorType for StackSimple<u8, C> {
+    type Error = CapacityError<()>;
+}
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::Write for StackSimple<u8, C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        match self.extend_from_slice_partial(buf) {
+            0 if !buf.is_empty() => Err(CapacityError::new(())),
+            n => Ok(n),
+        }
+    }
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+// O(len) per read like the std::io::Read impl, StackByteBuffer is the one for streaming
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::Read for StackSimple<u8, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_front(buf))
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::ErrorType for StackByteBuffer<C> {
+    type Error = CapacityError<()>;
+}
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::Write for StackByteBuffer<C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        match self.extend_from_slice_partial(buf) {
+            0 if !buf.is_empty() => Err(CapacityError::new(())),
+            n => Ok(n),
+        }
+    }
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::Read for StackByteBuffer<C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.read_into(buf))
+    }
+}
+#[cfg(feature = "embedded-io")]
+impl<const C: usize> embedded_io::BufRead for StackByteBuffer<C> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.unread())
+    }
+    fn consume(&mut self, n: usize) {
+        StackByteBuffer::consume(self, n)
+    }
+}
@@ -0,0 +1,151 @@
+use super::*;
+use core::iter::FusedIterator;
+
+// after a lot of inserts and deletes the next chain jumps all over the main_memory.
+// compact puts it back in storage order so walking the list is a straight scan again,
+// and then in the Split layout the elements are one contiguous slice.
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+
+    // permutes the nodes in place so that the element at virtual-index i lives in node i,
+    // and the free nodes are the ones after len. O(N), no extra memory.
+    // the elements that move get a new node, so their NodeHandles go stale.
+    pub fn compact(&mut self) {
+        // 1. the prev links get rebuilt at the end anyway, so use them to hold where each node has to go.
+        let mut current_node_i: Option<usize> = self.head_and_tail.map(|(head, _tail)| head);
+        let mut virtual_i: usize = 0;
+        while let Some(node_i) = current_node_i {
+            current_node_i = self.main_memory[node_i].next();
+            self.main_memory[node_i].set_prev(Some(virtual_i));
+            virtual_i += 1;
+        }
+
+        // 2. cycle through the swaps. each swap puts one element in its final place.
+        for node_i in 0..N {
+            while self.main_memory[node_i].is_live() {
+                let target_i = self.main_memory[node_i].prev().unwrap(); // unwrap bc we set it on every node in the list
+                if target_i == node_i {
+                    break;
+                }
+                let [a, b] = self.main_memory.links_disjoint_mut(node_i, target_i); // node_i != target_i
+                // the target can be a free node. then the liveness moves with the element, so the low bit of both generations flips.
+                // the generations stay with the slot, so bumping them makes the old handles to both slots stale
+                let bump: u32 = if a.is_live() == b.is_live() { 2 } else { 1 };
+                core::mem::swap(&mut a.prev, &mut b.prev);
+                a.generation = a.generation.wrapping_add(bump);
+                b.generation = b.generation.wrapping_add(bump);
+                self.main_memory.swap_elements(node_i, target_i);
+            }
+        }
+
+        // 3. the links are just the storage order now. the free nodes after len are all fresh again.
+        for i in 0..self.len {
+            self.main_memory[i].set_prev(if i == 0            { None } else { Some(i - 1) });
+            self.main_memory[i].set_next(if i == self.len - 1 { None } else { Some(i + 1) });
+        }
+        self.head_and_tail = if self.len == 0 { None } else { Some((0, self.len - 1)) };
+        self.free_list = None;
+        self.fresh_nodes_start = self.len;
+    }
+
+    // true if the element at virtual-index i lives in node i, like right after compact.
+    // O(len), it only looks at the links.
+    pub fn is_compact(&self) -> bool {
+        match self.head_and_tail {
+            None => true, // empty
+            Some((head, tail)) => head == 0 && tail == self.len - 1 && (0..self.len - 1).all(|i| self.main_memory[i].next() == Some(i + 1)),
+        }
+    }
+
+    // goes through the elements in storage order instead of list order. for sums, counts, searches and such
+    // where the order doesn't matter, it is a straight scan of the elements.
+    pub fn iter_unordered<'a>(&'a self) -> StackStructureIterUnordered<'a, T, N, Idx, L> {
+        StackStructureIterUnordered{
+            ms: self,
+            node_i: 0,
+            number_of_items_left: self.len,
+        }
+    }
+
+    pub fn iter_unordered_mut<'a>(&'a mut self) -> StackStructureIterUnorderedMut<'a, T, N, Idx, L> {
+        StackStructureIterUnorderedMut{
+            number_of_items_left: self.len,
+            node_i: 0,
+            ms: self,
+        }
+    }
+}
+
+// only the Split layout keeps the elements in an array of their own, so only it can give them out as a slice
+impl<T, const N: usize, Idx: LinkIndex> StackStructure<T, N, Idx, Split> {
+    // the elements in list order as one slice. None if the list is not in storage order, call compact first.
+    pub fn as_slice(&self) -> Option<&[T]> {
+        if !self.is_compact() {
+            return None;
+        }
+        Some(unsafe { core::slice::from_raw_parts(self.main_memory.elements().as_ptr() as *const T, self.len) }) // safe bc the first len nodes are the live ones
+    }
+
+    pub fn as_mut_slice(&mut self) -> Option<&mut [T]> {
+        if !self.is_compact() {
+            return None;
+        }
+        Some(unsafe { core::slice::from_raw_parts_mut(self.main_memory.elements_mut().as_mut_ptr() as *mut T, self.len) }) // safe bc the first len nodes are the live ones
+    }
+}
+
+
+pub struct StackStructureIterUnordered<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a StackStructure<T, N, Idx, L>,
+    node_i: usize, // the next node to look at
+    number_of_items_left: usize,
+}
+
+pub struct StackStructureIterUnorderedMut<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a mut StackStructure<T, N, Idx, L>,
+    node_i: usize, // the next node to look at
+    number_of_items_left: usize,
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureIterUnordered<'a, T, N, Idx, L> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.number_of_items_left == 0 {
+            return None; // don't scan the free nodes at the end
+        }
+        let ms: &'a StackStructure<T, N, Idx, L> = self.ms;
+        while !ms.main_memory[self.node_i].is_live() {
+            self.node_i += 1;
+        }
+        self.node_i += 1;
+        self.number_of_items_left -= 1;
+        ms.__element(self.node_i - 1)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.number_of_items_left, Some(self.number_of_items_left))
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureIterUnorderedMut<'a, T, N, Idx, L> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.number_of_items_left == 0 {
+            return None; // don't scan the free nodes at the end
+        }
+        while !self.ms.main_memory[self.node_i].is_live() {
+            self.node_i += 1;
+        }
+        self.node_i += 1;
+        self.number_of_items_left -= 1;
+        let item: *mut T = self.ms.__element_mut(self.node_i - 1).unwrap(); // unwrap bc the node is live
+        Some(unsafe { &mut *item }) // unsafe ok bc each node is given out only once
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.number_of_items_left, Some(self.number_of_items_left))
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureIterUnordered<'a, T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureIterUnorderedMut<'a, T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureIterUnordered<'a, T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureIterUnorderedMut<'a, T, N, Idx, L> {}
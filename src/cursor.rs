@@ -0,0 +1,223 @@
+use super::*;
+
+// a cursor points at one element of the list, or at the "ghost" position that sits between the tail and the head.
+// moving the cursor and editing at the cursor is O(1), no walking from the head.
+
+pub struct Cursor<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a StackStructure<T, N, Idx, L>,
+    current_node_i: Option<usize>, // internal-array-index. None if the cursor is on the ghost position
+    index: usize,                  // virtual-index. is == ms.len() when on the ghost position
+}
+
+pub struct CursorMut<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a mut StackStructure<T, N, Idx, L>,
+    current_node_i: Option<usize>, // internal-array-index. None if the cursor is on the ghost position
+    index: usize,                  // virtual-index. is == ms.len() when on the ghost position
+}
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+    // cursor on the first element, or on the ghost position if the list is empty
+    pub fn cursor_front<'a>(&'a self) -> Cursor<'a, T, N, Idx, L> {
+        Cursor{
+            current_node_i: self.head_and_tail.map(|(head, _tail)| head),
+            index: 0,
+            ms: self,
+        }
+    }
+    // cursor on the last element, or on the ghost position if the list is empty
+    pub fn cursor_back<'a>(&'a self) -> Cursor<'a, T, N, Idx, L> {
+        Cursor{
+            current_node_i: self.head_and_tail.map(|(_head, tail)| tail),
+            index: self.len.saturating_sub(1),
+            ms: self,
+        }
+    }
+    pub fn cursor_front_mut<'a>(&'a mut self) -> CursorMut<'a, T, N, Idx, L> {
+        CursorMut{
+            current_node_i: self.head_and_tail.map(|(head, _tail)| head),
+            index: 0,
+            ms: self,
+        }
+    }
+    pub fn cursor_back_mut<'a>(&'a mut self) -> CursorMut<'a, T, N, Idx, L> {
+        CursorMut{
+            current_node_i: self.head_and_tail.map(|(_head, tail)| tail),
+            index: self.len.saturating_sub(1),
+            ms: self,
+        }
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Clone for Cursor<'a, T, N, Idx, L> {
+    fn clone(&self) -> Self {
+        Cursor{
+            ms: self.ms,
+            current_node_i: self.current_node_i,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Cursor<'a, T, N, Idx, L> {
+    // None if the cursor is on the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current_node_i.map(|_| self.index)
+    }
+    // moving next from the tail goes to the ghost position, moving next from the ghost position goes to the head
+    pub fn move_next(&mut self) {
+        match self.current_node_i {
+            None => {
+                self.current_node_i = self.ms.head_and_tail.map(|(head, _tail)| head);
+                self.index = 0;
+            }
+            Some(current_node_i) => {
+                self.current_node_i = self.ms.main_memory[current_node_i].next();
+                self.index += 1;
+            }
+        }
+    }
+    // moving prev from the head goes to the ghost position, moving prev from the ghost position goes to the tail
+    pub fn move_prev(&mut self) {
+        match self.current_node_i {
+            None => {
+                self.current_node_i = self.ms.head_and_tail.map(|(_head, tail)| tail);
+                self.index = self.ms.len.saturating_sub(1);
+            }
+            Some(current_node_i) => {
+                self.current_node_i = self.ms.main_memory[current_node_i].prev();
+                self.index = match self.current_node_i {
+                    None => self.ms.len,
+                    Some(_) => self.index - 1,
+                };
+            }
+        }
+    }
+    pub fn current(&self) -> Option<&'a T> {
+        let ms: &'a StackStructure<T, N, Idx, L> = self.ms;
+        self.current_node_i.map(|i| ms.__element(i).unwrap()) // unwrap is safe here because each node in the list is live
+    }
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let ms: &'a StackStructure<T, N, Idx, L> = self.ms;
+        let next_node_i = match self.current_node_i {
+            None => ms.head_and_tail.map(|(head, _tail)| head),
+            Some(current_node_i) => ms.main_memory[current_node_i].next(),
+        };
+        next_node_i.map(|i| ms.__element(i).unwrap())
+    }
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let ms: &'a StackStructure<T, N, Idx, L> = self.ms;
+        let prev_node_i = match self.current_node_i {
+            None => ms.head_and_tail.map(|(_head, tail)| tail),
+            Some(current_node_i) => ms.main_memory[current_node_i].prev(),
+        };
+        prev_node_i.map(|i| ms.__element(i).unwrap())
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> CursorMut<'a, T, N, Idx, L> {
+    // None if the cursor is on the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current_node_i.map(|_| self.index)
+    }
+    pub fn move_next(&mut self) {
+        match self.current_node_i {
+            None => {
+                self.current_node_i = self.ms.head_and_tail.map(|(head, _tail)| head);
+                self.index = 0;
+            }
+            Some(current_node_i) => {
+                self.current_node_i = self.ms.main_memory[current_node_i].next();
+                self.index += 1;
+            }
+        }
+    }
+    pub fn move_prev(&mut self) {
+        match self.current_node_i {
+            None => {
+                self.current_node_i = self.ms.head_and_tail.map(|(_head, tail)| tail);
+                self.index = self.ms.len.saturating_sub(1);
+            }
+            Some(current_node_i) => {
+                self.current_node_i = self.ms.main_memory[current_node_i].prev();
+                self.index = match self.current_node_i {
+                    None => self.ms.len,
+                    Some(_) => self.index - 1,
+                };
+            }
+        }
+    }
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current_node_i.map(|i| self.ms.__element_mut(i).unwrap()) // unwrap is safe here because each node in the list is live
+    }
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next_node_i = match self.current_node_i {
+            None => self.ms.head_and_tail.map(|(head, _tail)| head),
+            Some(current_node_i) => self.ms.main_memory[current_node_i].next(),
+        };
+        next_node_i.map(|i| self.ms.__element_mut(i).unwrap())
+    }
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev_node_i = match self.current_node_i {
+            None => self.ms.head_and_tail.map(|(_head, tail)| tail),
+            Some(current_node_i) => self.ms.main_memory[current_node_i].prev(),
+        };
+        prev_node_i.map(|i| self.ms.__element_mut(i).unwrap())
+    }
+    // read-only cursor at the same position, borrowing from this one
+    pub fn as_cursor<'b>(&'b self) -> Cursor<'b, T, N, Idx, L> {
+        Cursor{
+            ms: self.ms,
+            current_node_i: self.current_node_i,
+            index: self.index,
+        }
+    }
+
+    // inserts the element before the current one. on the ghost position this inserts at the tail.
+    pub fn insert_before(&mut self, element: T) -> Result<(), CapacityError<T>> { // err if list is full
+        let new_node_i = match self.ms.__get_new_node_from_free_list() {
+            None => return Err(CapacityError::new(element)), // full
+            Some(new_node_i) => new_node_i,
+        };
+        self.ms.__put_element(new_node_i, element);
+        match self.current_node_i {
+            None => self.ms.__insert_node_as_tail(new_node_i),
+            Some(current_node_i) => match self.ms.main_memory[current_node_i].prev() {
+                None => self.ms.__insert_node_as_head(new_node_i),
+                Some(prev_node_i) => self.ms.__insert_node_after_node(new_node_i, prev_node_i),
+            }
+        }
+        self.ms.len += 1;
+        self.index += 1; // the current element (or the ghost) moved one place back
+        Ok(())
+    }
+    // inserts the element after the current one. on the ghost position this inserts at the head.
+    pub fn insert_after(&mut self, element: T) -> Result<(), CapacityError<T>> { // err if list is full
+        let new_node_i = match self.ms.__get_new_node_from_free_list() {
+            None => return Err(CapacityError::new(element)), // full
+            Some(new_node_i) => new_node_i,
+        };
+        self.ms.__put_element(new_node_i, element);
+        match self.current_node_i {
+            None => {
+                self.ms.__insert_node_as_head(new_node_i);
+                self.index += 1; // ghost index is always the len
+            }
+            Some(current_node_i) => self.ms.__insert_node_after_node(new_node_i, current_node_i),
+        }
+        self.ms.len += 1;
+        Ok(())
+    }
+    // removes the current element and moves the cursor to the next one. None if the cursor is on the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current_node_i = self.current_node_i?;
+        self.current_node_i = self.ms.main_memory[current_node_i].next(); // grab it before the node goes to the free list
+        Some(self.ms.__delete_node(current_node_i))
+    }
+    // returns the old value. err if the cursor is on the ghost position, the ghost is at index len.
+    pub fn replace_current(&mut self, element: T) -> Result<T, IndexOutOfBounds<T>> {
+        match self.current_node_i {
+            None => Err(IndexOutOfBounds::new(element, self.ms.len, self.ms.len)),
+            Some(current_node_i) => Ok(self.ms.__replace_element(current_node_i, element).unwrap()), // unwrap is safe here because each node in the list is live
+        }
+    }
+}
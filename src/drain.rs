@@ -0,0 +1,188 @@
+use super::*;
+use core::ops::{Bound, RangeBounds};
+use core::iter::FusedIterator;
+
+// range based removal and bulk insertion. the list is walked once to find the range, after that everything is O(1) per element.
+// like Vec, these panic if the range or the index is out of bounds.
+
+// (start, end) of the range in a collection of len elements, panics if out of bounds. StackSimple uses it too.
+pub(crate) fn __resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let start: usize = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.checked_add(1).expect("range start overflow"),
+        Bound::Unbounded => 0,
+    };
+    let end: usize = match range.end_bound() {
+        Bound::Included(&i) => i.checked_add(1).expect("range end overflow"),
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        core::panic!("range start (is {}) should be <= range end (is {})", start, end);
+    }
+    if end > len {
+        core::panic!("range end (is {}) should be <= len (is {})", end, len);
+    }
+    (start, end)
+}
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+
+    // inserts the items after the node, or at the head if None. err with the first item that didn't fit and the rest of the iterator.
+    fn __insert_iter_after_node<I: Iterator<Item=T>>(&mut self, mut node_before_i: Option<usize>, mut iter: I) -> Result<(), CapacityError<(T, I)>> {
+        while let Some(element) = iter.next() {
+            let new_node_i = match self.__get_new_node_from_free_list() {
+                None => return Err(CapacityError::new((element, iter))), // full
+                Some(new_node_i) => new_node_i,
+            };
+            self.__put_element(new_node_i, element);
+            match node_before_i {
+                None => self.__insert_node_as_head(new_node_i),
+                Some(i) => self.__insert_node_after_node(new_node_i, i),
+            }
+            self.len += 1;
+            node_before_i = Some(new_node_i);
+        }
+        Ok(())
+    }
+
+    // removes the elements in the range and gives them out as an owning double-ended iterator.
+    // whatever is not taken out of the iterator is dropped when the iterator is dropped.
+    pub fn drain<'a, R: RangeBounds<usize>>(&'a mut self, range: R) -> StackStructureDrain<'a, T, N, Idx, L> {
+        let (start, end) = __resolve_range(range, self.len);
+        let front_and_back: Option<(usize, usize)> = if start == end {
+            None
+        } else {
+            Some((self.__locate(start).unwrap(), self.__locate(end - 1).unwrap())) // unwrap bc we checked the range is within bounds
+        };
+        let node_before_i: Option<usize> = match front_and_back {
+            Some((front, _back)) => self.main_memory[front].prev(),
+            None if start == 0 => None,
+            None => self.__locate(start - 1),
+        };
+        StackStructureDrain{
+            ms: self,
+            current_nodes_i_forward_and_backward: front_and_back,
+            number_of_items_left: end - start,
+            node_before_i,
+        }
+    }
+
+    // inserts all the items of the iterator, in sequence, starting at the index. panics if index > len.
+    // the list is walked one time no matter how many items there are.
+    // if the list fills up, the items that fit stay inserted and the error has the first item that didn't fit and the rest of the iterator.
+    pub fn insert_iter<I: IntoIterator<Item=T>>(&mut self, index: usize, iter: I) -> Result<(), CapacityError<(T, I::IntoIter)>> {
+        if index > self.len {
+            core::panic!("insertion index (is {}) should be <= len (is {})", index, self.len);
+        }
+        let node_before_i: Option<usize> = if index == 0 { None } else { self.__locate(index - 1) };
+        self.__insert_iter_after_node(node_before_i, iter.into_iter())
+    }
+
+    // removes the range and puts the items of replace_with in its place.
+    // the returned iterator gives out the removed elements. the replacement happens when it is dropped or when finish is called,
+    // use finish to find out if everything fit, it has the same semantics as insert_iter.
+    pub fn splice<'a, R: RangeBounds<usize>, I: IntoIterator<Item=T>>(&'a mut self, range: R, replace_with: I) -> StackStructureSplice<'a, T, N, I::IntoIter, Idx, L> {
+        StackStructureSplice{
+            drain: self.drain(range),
+            replace_with: Some(replace_with.into_iter()),
+        }
+    }
+}
+
+
+pub struct StackStructureDrain<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a mut StackStructure<T, N, Idx, L>,
+    current_nodes_i_forward_and_backward: Option<(usize, usize)>, // none if there are no more items
+    number_of_items_left: usize,
+    node_before_i: Option<usize>, // the node before the range. None if the range starts at the head. it is not in the range so it stays put.
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureDrain<'a, T, N, Idx, L> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (forward_i, backward_i) = self.current_nodes_i_forward_and_backward?;
+        if forward_i == backward_i {
+            self.current_nodes_i_forward_and_backward = None;
+        } else {
+            self.current_nodes_i_forward_and_backward = Some((self.ms.main_memory[forward_i].next().unwrap(), backward_i)); // unwrap bc forward_i != backward_i so there is a next in the range
+        }
+        self.number_of_items_left -= 1;
+        Some(self.ms.__delete_node(forward_i))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.number_of_items_left, Some(self.number_of_items_left))
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> DoubleEndedIterator for StackStructureDrain<'a, T, N, Idx, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (forward_i, backward_i) = self.current_nodes_i_forward_and_backward?;
+        if forward_i == backward_i {
+            self.current_nodes_i_forward_and_backward = None;
+        } else {
+            self.current_nodes_i_forward_and_backward = Some((forward_i, self.ms.main_memory[backward_i].prev().unwrap())); // unwrap bc forward_i != backward_i so there is a prev in the range
+        }
+        self.number_of_items_left -= 1;
+        Some(self.ms.__delete_node(backward_i))
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureDrain<'a, T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureDrain<'a, T, N, Idx, L> {}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Drop for StackStructureDrain<'a, T, N, Idx, L> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+
+pub struct StackStructureSplice<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex = usize, L: Layout = Split> {
+    drain: StackStructureDrain<'a, T, N, Idx, L>,
+    replace_with: Option<I>, // taken when the replacement is done
+}
+
+impl<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex, L: Layout> StackStructureSplice<'a, T, N, I, Idx, L> {
+    // drops the removed elements that were not taken yet and inserts the replacement.
+    // err with the first item that didn't fit and the rest of the iterator. the items before it stay inserted.
+    pub fn finish(mut self) -> Result<(), CapacityError<(T, I)>> {
+        self.__replace()
+    }
+
+    fn __replace(&mut self) -> Result<(), CapacityError<(T, I)>> {
+        match self.replace_with.take() {
+            None => Ok(()), // done already
+            Some(replace_with) => {
+                (&mut self.drain).for_each(drop);
+                let node_before_i = self.drain.node_before_i;
+                self.drain.ms.__insert_iter_after_node(node_before_i, replace_with)
+            }
+        }
+    }
+}
+
+impl<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex, L: Layout> Iterator for StackStructureSplice<'a, T, N, I, Idx, L> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex, L: Layout> DoubleEndedIterator for StackStructureSplice<'a, T, N, I, Idx, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureSplice<'a, T, N, I, Idx, L> {}
+impl<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureSplice<'a, T, N, I, Idx, L> {}
+
+impl<'a, T, const N: usize, I: Iterator<Item=T>, Idx: LinkIndex, L: Layout> Drop for StackStructureSplice<'a, T, N, I, Idx, L> {
+    fn drop(&mut self) {
+        let _ = self.__replace(); // the items that didn't fit get dropped here. use finish to get them back.
+    }
+}
@@ -0,0 +1,208 @@
+use core::fmt;
+
+// the error types hand back the value that was passed in, so nothing gets dropped when a call fails.
+// the Debug impls don't need T: Debug so that .unwrap() works on any element type.
+
+
+// the collection is full
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    element: T,
+}
+impl<T> CapacityError<T> {
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+}
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError")
+    }
+}
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+impl<T> core::error::Error for CapacityError<T> {}
+
+
+// the index is past the end of the collection. T is () when there is no value to give back (e.g. delete).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfBounds<T = ()> {
+    element: T,
+    index: usize,
+    len: usize,
+}
+impl<T> IndexOutOfBounds<T> {
+    pub const fn new(element: T, index: usize, len: usize) -> Self {
+        Self { element, index, len }
+    }
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    // the len of the collection at the time of the call. it is not the len of the error, so there is no is_empty
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+impl<T> fmt::Debug for IndexOutOfBounds<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexOutOfBounds")
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+impl<T> fmt::Display for IndexOutOfBounds<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index out of bounds: the len is {} but the index is {}", self.len, self.index)
+    }
+}
+impl<T> core::error::Error for IndexOutOfBounds<T> {}
+
+
+// the handle points to an element that was already removed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct StaleHandle<T> {
+    element: T,
+}
+impl<T> StaleHandle<T> {
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+}
+impl<T> fmt::Debug for StaleHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StaleHandle")
+    }
+}
+impl<T> fmt::Display for StaleHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the handle is stale")
+    }
+}
+impl<T> core::error::Error for StaleHandle<T> {}
+
+
+// for the inserts that can fail in more than one way
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InsertError<T> {
+    Full(CapacityError<T>),
+    OutOfBounds(IndexOutOfBounds<T>),
+    StaleHandle(StaleHandle<T>), // only from the *_handle methods
+}
+impl<T> InsertError<T> {
+    pub fn element(&self) -> &T {
+        match self {
+            InsertError::Full(e)        => e.element(),
+            InsertError::OutOfBounds(e) => e.element(),
+            InsertError::StaleHandle(e) => e.element(),
+        }
+    }
+    pub fn into_inner(self) -> T {
+        match self {
+            InsertError::Full(e)        => e.into_inner(),
+            InsertError::OutOfBounds(e) => e.into_inner(),
+            InsertError::StaleHandle(e) => e.into_inner(),
+        }
+    }
+}
+impl<T> From<CapacityError<T>> for InsertError<T> {
+    fn from(e: CapacityError<T>) -> Self {
+        InsertError::Full(e)
+    }
+}
+impl<T> From<IndexOutOfBounds<T>> for InsertError<T> {
+    fn from(e: IndexOutOfBounds<T>) -> Self {
+        InsertError::OutOfBounds(e)
+    }
+}
+impl<T> From<StaleHandle<T>> for InsertError<T> {
+    fn from(e: StaleHandle<T>) -> Self {
+        InsertError::StaleHandle(e)
+    }
+}
+impl<T> fmt::Debug for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Full(e)        => f.debug_tuple("Full").field(e).finish(),
+            InsertError::OutOfBounds(e) => f.debug_tuple("OutOfBounds").field(e).finish(),
+            InsertError::StaleHandle(e) => f.debug_tuple("StaleHandle").field(e).finish(),
+        }
+    }
+}
+impl<T> fmt::Display for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Full(e)        => fmt::Display::fmt(e, f),
+            InsertError::OutOfBounds(e) => fmt::Display::fmt(e, f),
+            InsertError::StaleHandle(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+impl<T> core::error::Error for InsertError<T> {}
+
+
+// the collection filled up in the middle of an iterator. the first element that didn't fit and the rest of the iterator come back.
+// accepted is how many elements went in before that. C is the collection itself when it was being built from the iterator, see TryFromIterator.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TryExtendError<T, I, C = ()> {
+    element: T,
+    rest: I,
+    accepted: usize,
+    collection: C,
+}
+impl<T, I, C> TryExtendError<T, I, C> {
+    pub const fn new(element: T, rest: I, accepted: usize, collection: C) -> Self {
+        Self { element, rest, accepted, collection }
+    }
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+    pub fn collection(&self) -> &C {
+        &self.collection
+    }
+    // (the element that didn't fit, the rest of the iterator, the collection)
+    pub fn into_parts(self) -> (T, I, C) {
+        (self.element, self.rest, self.collection)
+    }
+}
+impl<T, I, C> fmt::Debug for TryExtendError<T, I, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryExtendError")
+            .field("accepted", &self.accepted)
+            .finish()
+    }
+}
+impl<T, I, C> fmt::Display for TryExtendError<T, I, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "insufficient capacity: the collection was full after {} elements", self.accepted)
+    }
+}
+impl<T, I, C> core::error::Error for TryExtendError<T, I, C> {}
@@ -0,0 +1,109 @@
+use super::*;
+
+// the fallible versions of FromIterator and Extend. those unwrap and panic when the capacity runs out, these stop
+// at the first element that doesn't fit and give it back together with the rest of the iterator.
+
+// FromIterator that can run out of capacity. on an error the elements that did fit are in the collection that comes back in the error.
+pub trait TryFromIterator<A>: Sized {
+    fn try_from_iter<I: IntoIterator<Item=A>>(iter: I) -> Result<Self, TryExtendError<A, I::IntoIter, Self>>;
+}
+
+// collect for the fixed capacity collections, without the panic.
+// e.g. let ms: Result<StackStructure<u8, 4>, _> = (0..10).try_collect();
+pub trait TryCollect: Iterator + Sized {
+    fn try_collect<C: TryFromIterator<Self::Item>>(self) -> Result<C, TryExtendError<Self::Item, Self, C>> {
+        C::try_from_iter(self)
+    }
+}
+impl<I: Iterator> TryCollect for I {}
+
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+    // pushes the elements to the back. ok with the number of elements pushed.
+    pub fn try_extend<I: IntoIterator<Item=T>>(&mut self, iter: I) -> Result<usize, TryExtendError<T, I::IntoIter>> {
+        let mut iter = iter.into_iter();
+        let mut accepted: usize = 0;
+        while let Some(element) = iter.next() {
+            if let Err(e) = self.push(element) {
+                return Err(TryExtendError::new(e.into_inner(), iter, accepted, ()));
+            }
+            accepted += 1;
+        }
+        Ok(accepted)
+    }
+}
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> TryFromIterator<T> for StackStructure<T, N, Idx, L> {
+    fn try_from_iter<I: IntoIterator<Item=T>>(iter: I) -> Result<Self, TryExtendError<T, I::IntoIter, Self>> {
+        let mut ms = Self::new();
+        match ms.try_extend(iter) {
+            Ok(_) => Ok(ms),
+            Err(e) => {
+                let accepted = e.accepted();
+                let (element, rest, ()) = e.into_parts();
+                Err(TryExtendError::new(element, rest, accepted, ms))
+            }
+        }
+    }
+}
+
+
+impl<T, const C: usize> StackSimple<T, C> {
+    // pushes the elements to the back. ok with the number of elements pushed.
+    pub fn try_extend<I: IntoIterator<Item=T>>(&mut self, iter: I) -> Result<usize, TryExtendError<T, I::IntoIter>> {
+        let mut iter = iter.into_iter();
+        let mut accepted: usize = 0;
+        while let Some(element) = iter.next() {
+            if let Err(e) = self.push(element) {
+                return Err(TryExtendError::new(e.into_inner(), iter, accepted, ()));
+            }
+            accepted += 1;
+        }
+        Ok(accepted)
+    }
+}
+
+impl<T, const C: usize> TryFromIterator<T> for StackSimple<T, C> {
+    fn try_from_iter<I: IntoIterator<Item=T>>(iter: I) -> Result<Self, TryExtendError<T, I::IntoIter, Self>> {
+        let mut ss = Self::new();
+        match ss.try_extend(iter) {
+            Ok(_) => Ok(ss),
+            Err(e) => {
+                let accepted = e.accepted();
+                let (element, rest, ()) = e.into_parts();
+                Err(TryExtendError::new(element, rest, accepted, ss))
+            }
+        }
+    }
+}
+
+
+impl<K: Ord, V, const N: usize> StackMap<K, V, N> {
+    // inserts the pairs. ok with the number of pairs inserted, the ones that replaced the value of a key already in the map count too.
+    // only a new key can run out of capacity.
+    pub fn try_extend<I: IntoIterator<Item=(K, V)>>(&mut self, iter: I) -> Result<usize, TryExtendError<(K, V), I::IntoIter>> {
+        let mut iter = iter.into_iter();
+        let mut accepted: usize = 0;
+        while let Some((key, value)) = iter.next() {
+            if let Err(e) = self.insert(key, value) {
+                return Err(TryExtendError::new(e.into_inner(), iter, accepted, ()));
+            }
+            accepted += 1;
+        }
+        Ok(accepted)
+    }
+}
+
+impl<K: Ord, V, const N: usize> TryFromIterator<(K, V)> for StackMap<K, V, N> {
+    fn try_from_iter<I: IntoIterator<Item=(K, V)>>(iter: I) -> Result<Self, TryExtendError<(K, V), I::IntoIter, Self>> {
+        let mut map = Self::new();
+        match map.try_extend(iter) {
+            Ok(_) => Ok(map),
+            Err(e) => {
+                let accepted = e.accepted();
+                let (element, rest, ()) = e.into_parts();
+                Err(TryExtendError::new(element, rest, accepted, map))
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+// a stable reference to one element of a StackStructure. stays valid through other insertions and deletions.
+// once the element is removed the handle goes stale, even if its slot gets reused by a new element later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    node_i: usize,     // internal-array-index
+    generation: u32,   // generation of the node at the time the handle was made
+}
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+    pub(crate) fn __handle_of_node(&self, node_i: usize) -> NodeHandle {
+        NodeHandle{
+            node_i,
+            generation: self.main_memory[node_i].generation,
+        }
+    }
+
+    // Some with the internal-array-index if the handle is not stale
+    pub(crate) fn __node_of_handle(&self, handle: NodeHandle) -> Option<usize> {
+        if handle.node_i >= N {
+            return None;
+        }
+        let node = &self.main_memory[handle.node_i];
+        if node.generation != handle.generation || !node.is_live() {
+            return None;
+        }
+        Some(handle.node_i)
+    }
+
+    pub fn get_by_handle(&self, handle: NodeHandle) -> Option<&T> { // none if the handle is stale
+        let node_i = self.__node_of_handle(handle)?;
+        self.__element(node_i)
+    }
+
+    pub fn get_mut_by_handle(&mut self, handle: NodeHandle) -> Option<&mut T> { // none if the handle is stale
+        let node_i = self.__node_of_handle(handle)?;
+        self.__element_mut(node_i)
+    }
+
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<T> { // none if the handle is stale
+        let node_i = self.__node_of_handle(handle)?;
+        Some(self.__delete_node(node_i))
+    }
+
+    pub fn insert_after_handle(&mut self, handle: NodeHandle, element: T) -> Result<NodeHandle, InsertError<T>> { // err if the handle is stale or if the list is full. the element comes back in the error
+        let current_node_i = match self.__node_of_handle(handle) {
+            None => return Err(StaleHandle::new(element).into()), // stale
+            Some(i) => i,
+        };
+        let new_node_i = match self.__get_new_node_from_free_list() {
+            None => return Err(CapacityError::new(element).into()), // full
+            Some(i) => i,
+        };
+        self.__put_element(new_node_i, element);
+        self.__insert_node_after_node(new_node_i, current_node_i);
+        self.len += 1;
+        Ok(self.__handle_of_node(new_node_i))
+    }
+}
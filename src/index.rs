@@ -0,0 +1,39 @@
+use core::fmt::Debug;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+// the integer type that the prev and next links of each node are stored as.
+// the max value of the type is the sentinel for "no link", so it takes the place of the Option
+// and a list with links of type Idx can have at most Idx::MAX nodes.
+// on 64-bit a usize link is 8 bytes, a u16 link is 2 bytes. pick the smallest one that fits the capacity.
+pub trait LinkIndex: Copy + Eq + Debug + sealed::Sealed {
+    const NONE: Self;
+    const MAX_CAPACITY: usize;
+    fn from_option(i: Option<usize>) -> Self;
+    fn into_option(self) -> Option<usize>;
+}
+
+macro_rules! impl_link_index {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+        impl LinkIndex for $t {
+            const NONE: Self = <$t>::MAX;
+            const MAX_CAPACITY: usize = <$t>::MAX as usize;
+            #[inline]
+            fn from_option(i: Option<usize>) -> Self {
+                match i {
+                    None => Self::NONE,
+                    Some(i) => i as $t, // fits bc N <= MAX_CAPACITY is checked at compile time
+                }
+            }
+            #[inline]
+            fn into_option(self) -> Option<usize> {
+                if self == Self::NONE { None } else { Some(self as usize) }
+            }
+        }
+    )*};
+}
+
+impl_link_index!(u8, u16, u32, usize);
@@ -1,4 +1,5 @@
 use super::*;
+use core::mem::MaybeUninit;
 use core::iter::{
     DoubleEndedIterator,
     FusedIterator,
@@ -7,33 +8,33 @@ use core::iter::{
 
 
 
-pub struct StackStructureIterator<T: Debug, const N: usize> {
-    ms: StackStructure<T, N>,
+pub struct StackStructureIterator<T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: StackStructure<T, N, Idx, L>,
 }
 
-pub struct StackStructureIteratorRef<'a, T: Debug, const N: usize> {
-    ms: &'a StackStructure<T, N>,
+pub struct StackStructureIteratorRef<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a StackStructure<T, N, Idx, L>,
     current_nodes_i_forward_and_backward: Option<(usize, usize)>,  // none if there are no more items
     number_of_items_served: usize,
 }
 
-pub struct StackStructureIteratorRefMut<'a, T: Debug, const N: usize> {
-    ms: &'a mut StackStructure<T, N>,
+pub struct StackStructureIteratorRefMut<'a, T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a mut StackStructure<T, N, Idx, L>,
     current_nodes_i_forward_and_backward: Option<(usize, usize)>,  // none if there are no more items
     number_of_items_served: usize,
 }
 
 
 
-impl<T: Debug, const N: usize> StackStructure<T, N> {
-    pub fn iter<'a>(&'a self) -> StackStructureIteratorRef<'a, T, N> {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+    pub fn iter<'a>(&'a self) -> StackStructureIteratorRef<'a, T, N, Idx, L> {
         StackStructureIteratorRef{
             ms: self,
             current_nodes_i_forward_and_backward: self.head_and_tail,
             number_of_items_served: 0,
         }
     }
-    pub fn iter_mut<'a>(&'a mut self) -> StackStructureIteratorRefMut<'a, T, N> {
+    pub fn iter_mut<'a>(&'a mut self) -> StackStructureIteratorRefMut<'a, T, N, Idx, L> {
         StackStructureIteratorRefMut{
             current_nodes_i_forward_and_backward: self.head_and_tail,
             ms: self,
@@ -42,7 +43,7 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
     }
 }
 
-impl<T: Debug, const N: usize> FromIterator<T> for StackStructure<T, N> {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> FromIterator<T> for StackStructure<T, N, Idx, L> {
     fn from_iter<Iter: IntoIterator<Item=T>>(iter: Iter) -> Self {
         let mut ms = Self::new();
         for item in iter {
@@ -52,9 +53,9 @@ impl<T: Debug, const N: usize> FromIterator<T> for StackStructure<T, N> {
     }    
 }
 
-impl<T: Debug, const N: usize> IntoIterator for StackStructure<T, N> {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> IntoIterator for StackStructure<T, N, Idx, L> {
     type Item = T;
-    type IntoIter = StackStructureIterator<T, N>;
+    type IntoIter = StackStructureIterator<T, N, Idx, L>;
     fn into_iter(self) -> Self::IntoIter {
         StackStructureIterator{
             ms: self
@@ -62,7 +63,7 @@ impl<T: Debug, const N: usize> IntoIterator for StackStructure<T, N> {
     }
 } 
 
-impl<T: Debug, const N: usize> Iterator for StackStructureIterator<T, N> {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureIterator<T, N, Idx, L> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         match self.ms.len() {
@@ -75,17 +76,18 @@ impl<T: Debug, const N: usize> Iterator for StackStructureIterator<T, N> {
     }
 }
 
-impl<'a, T: Debug, const N: usize> Iterator for StackStructureIteratorRef<'a, T, N> {
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureIteratorRef<'a, T, N, Idx, L> {
     type Item = &'a T;
+    #[allow(clippy::needless_return)] // the empty case returns early, the same in all four
     fn next(&mut self) -> Option<Self::Item> {
         match self.current_nodes_i_forward_and_backward {
             None => return None,
             Some((ref mut forward_i, ref backward_i)) => {
-                let item = self.ms.main_memory[*forward_i].element.as_ref().unwrap();
+                let item = self.ms.__element(*forward_i).unwrap();
                 if forward_i == backward_i {
                     self.current_nodes_i_forward_and_backward = None;
                 } else {
-                    *forward_i = self.ms.main_memory[*forward_i].next.unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
+                    *forward_i = self.ms.main_memory[*forward_i].next().unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
                 }
                 self.number_of_items_served += 1;
                 Some(item)
@@ -98,20 +100,21 @@ impl<'a, T: Debug, const N: usize> Iterator for StackStructureIteratorRef<'a, T,
     }
 }
 
-impl<'a, T: Debug, const N: usize> Iterator for StackStructureIteratorRefMut<'a, T, N> {
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureIteratorRefMut<'a, T, N, Idx, L> {
     type Item = &'a mut T;
+    #[allow(clippy::needless_return)]
     fn next(&mut self) -> Option<Self::Item> {
         match self.current_nodes_i_forward_and_backward {
             None => return None,
             Some((ref mut forward_i, ref backward_i)) => {
-                let item = self.ms.main_memory[*forward_i].element.as_mut().unwrap();
+                let item: *mut T = self.ms.__element_mut(*forward_i).unwrap();
                 if forward_i == backward_i {
                     self.current_nodes_i_forward_and_backward = None;
                 } else {
-                    *forward_i = self.ms.main_memory[*forward_i].next.unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
+                    *forward_i = self.ms.main_memory[*forward_i].next().unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
                 }
                 self.number_of_items_served += 1;
-                unsafe { Some(&mut *(item as *mut T)) }
+                unsafe { Some(&mut *item) }
             } 
         }
     }
@@ -121,7 +124,7 @@ impl<'a, T: Debug, const N: usize> Iterator for StackStructureIteratorRefMut<'a,
     }
 }
 
-impl<T: Debug, const N: usize> DoubleEndedIterator for StackStructureIterator<T, N> {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> DoubleEndedIterator for StackStructureIterator<T, N, Idx, L> {
     fn next_back(&mut self) -> Option<Self::Item> {
         match self.ms.len() {
             0 => None,
@@ -130,16 +133,17 @@ impl<T: Debug, const N: usize> DoubleEndedIterator for StackStructureIterator<T,
     }
 }
 
-impl<'a, T: Debug, const N: usize> DoubleEndedIterator for StackStructureIteratorRef<'a, T, N> {
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> DoubleEndedIterator for StackStructureIteratorRef<'a, T, N, Idx, L> {
+    #[allow(clippy::needless_return)]
     fn next_back(&mut self) -> Option<Self::Item> {
         match self.current_nodes_i_forward_and_backward {
             None => return None,
             Some((ref forward_i, ref mut backward_i)) => {
-                let item = self.ms.main_memory[*backward_i].element.as_ref().unwrap();
+                let item = self.ms.__element(*backward_i).unwrap();
                 if forward_i == backward_i {
                     self.current_nodes_i_forward_and_backward = None;
                 } else {
-                    *backward_i = self.ms.main_memory[*backward_i].prev.unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
+                    *backward_i = self.ms.main_memory[*backward_i].prev().unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
                 }
                 self.number_of_items_served += 1;
                 Some(item)
@@ -148,35 +152,36 @@ impl<'a, T: Debug, const N: usize> DoubleEndedIterator for StackStructureIterato
     }
 }
 
-impl<'a, T: Debug, const N: usize> DoubleEndedIterator for StackStructureIteratorRefMut<'a, T, N> {
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> DoubleEndedIterator for StackStructureIteratorRefMut<'a, T, N, Idx, L> {
+    #[allow(clippy::needless_return)]
     fn next_back(&mut self) -> Option<Self::Item> {
         match self.current_nodes_i_forward_and_backward {
             None => return None,
             Some((ref forward_i, ref mut backward_i)) => {
-                let item = self.ms.main_memory[*backward_i].element.as_mut().unwrap();
+                let item: *mut T = self.ms.__element_mut(*backward_i).unwrap();
                 if forward_i == backward_i {
                     self.current_nodes_i_forward_and_backward = None;
                 } else {
-                    *backward_i = self.ms.main_memory[*backward_i].prev.unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
+                    *backward_i = self.ms.main_memory[*backward_i].prev().unwrap(); // unwrap bc this scope only happens if forward_i != backward_i so there will always be a Some(prev) in this specific scope  
                 }
                 self.number_of_items_served += 1;
-                unsafe { Some(&mut *(item as *mut T)) }
+                unsafe { Some(&mut *item) }
             } 
         }        
     }
 }
 
 // must implement the size_hint Iterator method on the Iterator plementations for these structs, the ExactSizeIterator::len method uses the size_hint iterator method and the low and high must be the same
-impl<T: Debug, const N: usize> ExactSizeIterator for StackStructureIterator<T, N> {}
-impl<'a, T: Debug, const N: usize> ExactSizeIterator for StackStructureIteratorRef<'a, T, N> {}
-impl<'a, T: Debug, const N: usize> ExactSizeIterator for StackStructureIteratorRefMut<'a, T, N> {}
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureIterator<T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureIteratorRef<'a, T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureIteratorRefMut<'a, T, N, Idx, L> {}
 
 
-impl<'a, T: Debug, const N: usize> FusedIterator for StackStructureIterator<T, N> {}
-impl<'a, T: Debug, const N: usize> FusedIterator for StackStructureIteratorRef<'a, T, N> {}
-impl<'a, T: Debug, const N: usize> FusedIterator for StackStructureIteratorRefMut<'a, T, N> {}
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureIterator<T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureIteratorRef<'a, T, N, Idx, L> {}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureIteratorRefMut<'a, T, N, Idx, L> {}
 
-impl<T: Debug, const N: usize> Extend<T> for StackStructure<T, N> {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> Extend<T> for StackStructure<T, N, Idx, L> {
     fn extend<Iter: IntoIterator<Item=T>>(&mut self, iter: Iter) {
         for item in iter {
             self.push(item).unwrap(); // will panic if not enough room!
@@ -186,10 +191,10 @@ impl<T: Debug, const N: usize> Extend<T> for StackStructure<T, N> {
 
 
 
-pub struct StackStructureRChunks<'a, T: Debug, const N: usize, const C: usize> {
-    iterator: StackStructureIteratorRef<'a, T, N>,
+pub struct StackStructureRChunks<'a, T, const N: usize, const C: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    iterator: StackStructureIteratorRef<'a, T, N, Idx, L>,
 }
-impl<'a, T: Debug, const N: usize, const C: usize> Iterator for StackStructureRChunks<'a, T, N, C> {
+impl<'a, T, const N: usize, const C: usize, Idx: LinkIndex, L: Layout> Iterator for StackStructureRChunks<'a, T, N, C, Idx, L> {
     type Item = StackSimple<&'a T, C>;
     fn next(&mut self) -> Option<Self::Item> {
         if self.iterator.len() == 0 {
@@ -204,6 +209,7 @@ impl<'a, T: Debug, const N: usize, const C: usize> Iterator for StackStructureRC
         }
         Some(unsafe { StackSimple::from_maybeuninit_data_and_len(chunk_data, chunk_len) }) // unsafe ok bc we just wrote to the first len items in the chunk_data
     }
+    #[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs rust 1.87
     fn size_hint(&self) -> (usize, Option<usize>) {
         let mut number_of_chunks_left = self.iterator.len() / C;
         if self.iterator.len() % C != 0 {
@@ -212,15 +218,15 @@ impl<'a, T: Debug, const N: usize, const C: usize> Iterator for StackStructureRC
         (number_of_chunks_left, Some(number_of_chunks_left))
     }
 }
-impl<'a, T: Debug, const N: usize, const C: usize> ExactSizeIterator for StackStructureRChunks<'a, T, N, C> {}
-impl<'a, T: Debug, const N: usize, const C: usize> FusedIterator for StackStructureRChunks<'a, T, N, C> {}
+impl<'a, T, const N: usize, const C: usize, Idx: LinkIndex, L: Layout> ExactSizeIterator for StackStructureRChunks<'a, T, N, C, Idx, L> {}
+impl<'a, T, const N: usize, const C: usize, Idx: LinkIndex, L: Layout> FusedIterator for StackStructureRChunks<'a, T, N, C, Idx, L> {}
 
 
-impl<'a, T: Debug, const N: usize> StackStructure<T, N> {
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
     // this method creates a reference for each element in the chunk at the same time. don't create large chunks.
     // the chunk size is reserved at compile time on the stack though. and the size is the size of a reference times the number of references in the chunk.
-    pub fn rchunks<const C: usize>(&'a self) -> StackStructureRChunks<'a, T, N, C> {
-        StackStructureRChunks::<'a, T, N, C>{
+    pub fn rchunks<const C: usize>(&'a self) -> StackStructureRChunks<'a, T, N, C, Idx, L> {
+        StackStructureRChunks::<'a, T, N, C, Idx, L>{
             iterator: self.iter(),
         }
     }
@@ -0,0 +1,182 @@
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use crate::index::LinkIndex;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+// how the nodes of a StackStructure sit in memory. the 4th generic parameter of StackStructure picks one:
+// Split keeps the elements in one array and the links in another, so a scan over the elements doesn't pull the links
+// through the cache and the other way around. after compact the elements are one contiguous slice. this is the default.
+// Interleaved keeps each element next to its links, so walking the list touches one place per node instead of two.
+// StackStructure<u32, 64> is split, StackStructure<u32, 64, usize, Interleaved> is interleaved.
+pub trait Layout: sealed::Sealed {
+    #[doc(hidden)]
+    type Storage<T, Idx: LinkIndex, const N: usize>: NodeStorage<T, Idx>;
+}
+
+pub struct Split;
+pub struct Interleaved;
+
+impl sealed::Sealed for Split {}
+impl sealed::Sealed for Interleaved {}
+impl Layout for Split {
+    type Storage<T, Idx: LinkIndex, const N: usize> = SplitStorage<T, Idx, N>;
+}
+impl Layout for Interleaved {
+    type Storage<T, Idx: LinkIndex, const N: usize> = InterleavedStorage<T, Idx, N>;
+}
+
+
+// the links of one node.
+// whether the node is live is the low bit of the generation, so the occupancy of the nodes is a packed bit in a word every node has anyway,
+// and there is no Option discriminant per element. (a separate [u64; (N+63)/64] bitmap can't be sized from N on stable rust.)
+#[derive(Debug)]
+pub struct Node<Idx> {
+    pub(crate) prev: Idx,          // Idx::NONE if this is the first item
+    pub(crate) next: Idx,          // Idx::NONE if this is the last item
+    pub(crate) generation: u32,    // odd while the node holds an element, even while it is free. bumped on every put and take, so old NodeHandles to it go stale
+}
+impl<Idx: LinkIndex> Node<Idx> {
+    const FREE: Self = Node{ prev: Idx::NONE, next: Idx::NONE, generation: 0 };
+
+    // the links as Option<usize> so the rest of the code doesn't care about the sentinel
+    #[inline]
+    pub(crate) fn prev(&self) -> Option<usize> {
+        self.prev.into_option()
+    }
+    #[inline]
+    pub(crate) fn next(&self) -> Option<usize> {
+        self.next.into_option()
+    }
+    #[inline]
+    pub(crate) fn set_prev(&mut self, i: Option<usize>) {
+        self.prev = Idx::from_option(i);
+    }
+    #[inline]
+    pub(crate) fn set_next(&mut self, i: Option<usize>) {
+        self.next = Idx::from_option(i);
+    }
+    #[inline]
+    pub(crate) fn is_live(&self) -> bool {
+        self.generation & 1 == 1
+    }
+}
+
+
+// what StackStructure needs from a layout. storage[i] is the links of node i, the element of node i goes through element and element_mut.
+// the element is initialized if and only if the links are live, StackStructure keeps that up.
+#[doc(hidden)]
+pub trait NodeStorage<T, Idx: LinkIndex>: Index<usize, Output = Node<Idx>> + IndexMut<usize> {
+    const EMPTY: Self; // all nodes free. a const so StackStructure::new can stay a const fn
+    fn element(&self, node_i: usize) -> &MaybeUninit<T>;
+    fn element_mut(&mut self, node_i: usize) -> &mut MaybeUninit<T>;
+    // panics if a == b
+    fn elements_disjoint_mut(&mut self, a: usize, b: usize) -> [&mut MaybeUninit<T>; 2];
+    fn links_disjoint_mut(&mut self, a: usize, b: usize) -> [&mut Node<Idx>; 2];
+    fn swap_elements(&mut self, a: usize, b: usize);
+}
+
+
+#[doc(hidden)]
+pub struct SplitStorage<T, Idx, const N: usize> {
+    elements: [MaybeUninit<T>; N],
+    links: [Node<Idx>; N],
+}
+impl<T, Idx: LinkIndex, const N: usize> SplitStorage<T, Idx, N> {
+    // all N elements, for the slice after compact
+    pub(crate) fn elements(&self) -> &[MaybeUninit<T>; N] {
+        &self.elements
+    }
+    pub(crate) fn elements_mut(&mut self) -> &mut [MaybeUninit<T>; N] {
+        &mut self.elements
+    }
+}
+impl<T, Idx: LinkIndex, const N: usize> Index<usize> for SplitStorage<T, Idx, N> {
+    type Output = Node<Idx>;
+    #[inline]
+    fn index(&self, node_i: usize) -> &Node<Idx> {
+        &self.links[node_i]
+    }
+}
+impl<T, Idx: LinkIndex, const N: usize> IndexMut<usize> for SplitStorage<T, Idx, N> {
+    #[inline]
+    fn index_mut(&mut self, node_i: usize) -> &mut Node<Idx> {
+        &mut self.links[node_i]
+    }
+}
+impl<T, Idx: LinkIndex, const N: usize> NodeStorage<T, Idx> for SplitStorage<T, Idx, N> {
+    const EMPTY: Self = Self {
+        elements: [const { MaybeUninit::uninit() }; N],
+        links: [const { Node::FREE }; N],
+    };
+    #[inline]
+    fn element(&self, node_i: usize) -> &MaybeUninit<T> {
+        &self.elements[node_i]
+    }
+    #[inline]
+    fn element_mut(&mut self, node_i: usize) -> &mut MaybeUninit<T> {
+        &mut self.elements[node_i]
+    }
+    fn elements_disjoint_mut(&mut self, a: usize, b: usize) -> [&mut MaybeUninit<T>; 2] {
+        self.elements.get_disjoint_mut([a, b]).unwrap() // unwrap bc the caller makes sure a != b
+    }
+    fn links_disjoint_mut(&mut self, a: usize, b: usize) -> [&mut Node<Idx>; 2] {
+        self.links.get_disjoint_mut([a, b]).unwrap() // unwrap bc the caller makes sure a != b
+    }
+    fn swap_elements(&mut self, a: usize, b: usize) {
+        self.elements.swap(a, b);
+    }
+}
+
+
+struct InterleavedNode<T, Idx> {
+    links: Node<Idx>,
+    element: MaybeUninit<T>,
+}
+
+#[doc(hidden)]
+pub struct InterleavedStorage<T, Idx, const N: usize> {
+    nodes: [InterleavedNode<T, Idx>; N],
+}
+impl<T, Idx: LinkIndex, const N: usize> Index<usize> for InterleavedStorage<T, Idx, N> {
+    type Output = Node<Idx>;
+    #[inline]
+    fn index(&self, node_i: usize) -> &Node<Idx> {
+        &self.nodes[node_i].links
+    }
+}
+impl<T, Idx: LinkIndex, const N: usize> IndexMut<usize> for InterleavedStorage<T, Idx, N> {
+    #[inline]
+    fn index_mut(&mut self, node_i: usize) -> &mut Node<Idx> {
+        &mut self.nodes[node_i].links
+    }
+}
+impl<T, Idx: LinkIndex, const N: usize> NodeStorage<T, Idx> for InterleavedStorage<T, Idx, N> {
+    const EMPTY: Self = Self {
+        nodes: [const { InterleavedNode{ links: Node::FREE, element: MaybeUninit::uninit() } }; N],
+    };
+    #[inline]
+    fn element(&self, node_i: usize) -> &MaybeUninit<T> {
+        &self.nodes[node_i].element
+    }
+    #[inline]
+    fn element_mut(&mut self, node_i: usize) -> &mut MaybeUninit<T> {
+        &mut self.nodes[node_i].element
+    }
+    fn elements_disjoint_mut(&mut self, a: usize, b: usize) -> [&mut MaybeUninit<T>; 2] {
+        let [a, b] = self.nodes.get_disjoint_mut([a, b]).unwrap(); // unwrap bc the caller makes sure a != b
+        [&mut a.element, &mut b.element]
+    }
+    fn links_disjoint_mut(&mut self, a: usize, b: usize) -> [&mut Node<Idx>; 2] {
+        let [a, b] = self.nodes.get_disjoint_mut([a, b]).unwrap(); // unwrap bc the caller makes sure a != b
+        [&mut a.links, &mut b.links]
+    }
+    fn swap_elements(&mut self, a: usize, b: usize) {
+        if a != b {
+            let [a, b] = self.elements_disjoint_mut(a, b);
+            core::mem::swap(a, b);
+        }
+    }
+}
@@ -1,7 +1,6 @@
 #![no_std] // portant!
 
 use core::fmt::Debug;
-use core::mem::MaybeUninit;
 
 mod iterators;
 pub use iterators::*;
@@ -12,145 +11,285 @@ pub use simple::*;
 mod map;
 pub use map::*;
 
-#[cfg(test)] extern crate std;
+mod string;
+pub use string::*;
+
+mod buffer;
+pub use buffer::*;
+
+mod ring;
+pub use ring::*;
+
+mod cursor;
+pub use cursor::*;
+
+mod handle;
+pub use handle::*;
+
+mod error;
+pub use error::*;
+
+mod index;
+pub use index::*;
+
+mod layout;
+pub use layout::{Layout, Split, Interleaved};
+use layout::{Node, NodeStorage};
+
+mod sort;
+
+mod retain;
+pub use retain::*;
+
+mod drain;
+pub use drain::*;
+
+mod compact;
+pub use compact::*;
+
+mod traits;
+
+mod fallible;
+pub use fallible::*;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(any(test, feature = "std"))] extern crate std;
 #[cfg(test)] use std::*;
 #[cfg(test)] mod tests;
 
 
 
 
-#[derive(Debug)]
-struct Node<T: Debug> {
-    element: Option<T>,  // will be None if this node is free //  // not needed for this to be an option but i like it. it can also be default and then we can save the cost of overwriting the bytes but idk.
-    prev: Option<usize>, // none if this is the first item
-    next: Option<usize>, // none if this is the last item
-}
-
-#[derive(Debug)]
-pub struct StackStructure<T: Debug, const N: usize> {
-    pub(crate) main_memory: [Node<T>; N],
+// the nodes sit in main_memory, laid out the way L says (see layout.rs). main_memory[i] is the links of node i,
+// the element of node i goes through the __element helpers below.
+pub struct StackStructure<T, const N: usize, Idx: LinkIndex = usize, L: Layout = Split> {
+    pub(crate) main_memory: L::Storage<T, Idx, N>, // the element of node i is initialized if and only if main_memory[i] is live
     head_and_tail: Option<(usize, usize)>,      // None if list is empty// index into the main_memory
-    free_list: Option<usize>,                   // points to the first free node. None if list is full.
+    free_list: Option<usize>,                   // points to the first free node that was used before. None if there is none.
+    fresh_nodes_start: usize,                   // the nodes from here to N were never used. they are free without being in the free_list.
     len: usize,
+    lowest_free_first: bool,                    // the allocation mode. true keeps the free_list sorted so the lowest free node is used next
 }
-impl<T: Debug, const N: usize> StackStructure<T, N> {
-    pub fn new() -> Self {
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+    // compile time check that the indexes of the nodes fit in Idx without hitting the sentinel
+    const N_FITS_IN_IDX: () = assert!(N <= Idx::MAX_CAPACITY, "the capacity N is too big for the link index type Idx");
+
+    // const so that a list can be put in a static.
+    // the free nodes are handed out from fresh_nodes_start first, so there is no free list chain to build here.
+    pub const fn new() -> Self {
+        let () = Self::N_FITS_IN_IDX;
         Self {
-            main_memory: {
-                let mut m: [MaybeUninit<Node<T>>; N] = [const { MaybeUninit::uninit() }; N];
-                for (i, element) in (&mut m[..]).into_iter().enumerate() {
-                    element.write(
-                        Node{
-                            element: None,
-                            prev: if i == 0   { None } else { Some(i-1) },
-                            next: if i == N-1 { None } else { Some(i+1) },
-                        }
-                    );
-                }
-                //unsafe { core::mem::transmute::<_, [Node<T>; N]>(m) } // https://github.com/rust-lang/rust/issues/62875
-                let done = unsafe { core::ptr::read((&m as *const [MaybeUninit<Node<T>>; N]).cast::<[Node<T>; N]>()) };
-                core::mem::forget(m);
-                done
-            },
+            main_memory: <L::Storage<T, Idx, N> as NodeStorage<T, Idx>>::EMPTY,
             head_and_tail: None,
-            free_list: Some(0),
+            free_list: None,
+            fresh_nodes_start: 0,
             len: 0,
+            lowest_free_first: false,
         }
     }
+
+    // a list in the lowest-free-first allocation mode, see set_lowest_free_first
+    pub const fn new_lowest_free_first() -> Self {
+        let mut ms = Self::new();
+        ms.lowest_free_first = true;
+        ms
+    }
     
     fn __get_new_node_from_free_list(&mut self) -> Option<usize/*internal-array-index*/> { // None if full
         match self.free_list {
             None => {
-                return None; // full
+                if self.fresh_nodes_start == N {
+                    return None; // full
+                }
+                self.fresh_nodes_start += 1;
+                Some(self.fresh_nodes_start - 1)
             }
             Some(new_node_i) => {
-                self.free_list = self.main_memory[new_node_i].next;
-                if let Some(i) = self.main_memory[new_node_i].next {
-                    self.main_memory[i].prev = None; // now this one becomes the first free node // this might not be needed but i like it
+                self.free_list = self.main_memory[new_node_i].next();
+                if let Some(i) = self.main_memory[new_node_i].next() {
+                    self.main_memory[i].set_prev(None); // now this one becomes the first free node // this might not be needed but i like it
                 }
                 Some(new_node_i)
             }
         }
     }
     
+    #[inline]
+    pub(crate) fn __element(&self, node_i: usize) -> Option<&T> { // none if the node is free
+        if !self.main_memory[node_i].is_live() {
+            return None;
+        }
+        Some(unsafe { self.main_memory.element(node_i).assume_init_ref() }) // safe bc live nodes are initialized
+    }
+    #[inline]
+    pub(crate) fn __element_mut(&mut self, node_i: usize) -> Option<&mut T> { // none if the node is free
+        if !self.main_memory[node_i].is_live() {
+            return None;
+        }
+        Some(unsafe { self.main_memory.element_mut(node_i).assume_init_mut() }) // safe bc live nodes are initialized
+    }
+    // the node must be free, otherwise the old element is leaked
+    #[inline]
+    pub(crate) fn __put_element(&mut self, node_i: usize, element: T) {
+        debug_assert!(!self.main_memory[node_i].is_live());
+        self.main_memory.element_mut(node_i).write(element);
+        self.main_memory[node_i].generation = self.main_memory[node_i].generation.wrapping_add(1);
+    }
+    #[inline]
+    pub(crate) fn __take_element(&mut self, node_i: usize) -> Option<T> { // none if the node is free
+        if !self.main_memory[node_i].is_live() {
+            return None;
+        }
+        self.main_memory[node_i].generation = self.main_memory[node_i].generation.wrapping_add(1);
+        Some(unsafe { self.main_memory.element(node_i).assume_init_read() }) // safe bc it was live, and now it is marked free so it won't be read again
+    }
+    #[inline]
+    pub(crate) fn __replace_element(&mut self, node_i: usize, element: T) -> Option<T> { // some with the old element if the node was live
+        match self.__element_mut(node_i) {
+            Some(old) => Some(core::mem::replace(old, element)),
+            None => {
+                self.__put_element(node_i, element);
+                None
+            }
+        }
+    }
+
+    // the allocation mode. by default a new element goes into the most recently freed node, O(1).
+    // in the lowest-free-first mode it goes into the lowest free node, so the live elements stay low in the main_memory
+    // without moving them, unlike compact. the free_list is kept sorted for that, which makes every delete O(number of free nodes).
+    pub fn set_lowest_free_first(&mut self, lowest_free_first: bool) {
+        if lowest_free_first && !self.lowest_free_first {
+            self.sort_free_list();
+        }
+        self.lowest_free_first = lowest_free_first;
+    }
+    pub fn is_lowest_free_first(&self) -> bool {
+        self.lowest_free_first
+    }
+
+    // rebuilds the free_list in storage order once, so the next elements go into the lowest free nodes first
+    // instead of the most recently freed ones. the free nodes at the end go back to being fresh. O(N).
+    // outside of the lowest-free-first mode the deletes after this push onto the front of the free_list again.
+    pub fn sort_free_list(&mut self) {
+        while self.fresh_nodes_start > 0 && !self.main_memory[self.fresh_nodes_start - 1].is_live() {
+            self.fresh_nodes_start -= 1;
+        }
+        self.free_list = None;
+        for node_i in (0..self.fresh_nodes_start).rev() {
+            if !self.main_memory[node_i].is_live() {
+                self.main_memory[node_i].set_next(self.free_list);
+                self.free_list = Some(node_i);
+            }
+        }
+    }
+    
     fn __insert_node_after_node(&mut self, new_node_i: usize, current_node_i: usize) {
-        self.main_memory[new_node_i].prev = Some(current_node_i);
-        match self.main_memory[current_node_i].next {
+        self.main_memory[new_node_i].set_prev(Some(current_node_i));
+        match self.main_memory[current_node_i].next() {
             None => {
                 // tail
-                self.main_memory[new_node_i].next = None;
+                self.main_memory[new_node_i].set_next(None);
                 self.head_and_tail.as_mut().unwrap().1 = new_node_i;
             }
             Some(next_node_i) => {
                 // in the middle
-                self.main_memory[new_node_i].next = Some(next_node_i);
-                self.main_memory[next_node_i].prev = Some(new_node_i);
+                self.main_memory[new_node_i].set_next(Some(next_node_i));
+                self.main_memory[next_node_i].set_prev(Some(new_node_i));
             }
         }
-        self.main_memory[current_node_i].next = Some(new_node_i);
+        self.main_memory[current_node_i].set_next(Some(new_node_i));
     }
 
-    
-    // optimize to start from tail if len - insertion_index < len / 2
-    pub fn insert(&mut self, insertion_index: usize, element: T) -> Result<(), ()> { // err if list is full or if index is out of bounds
-        match self.__get_new_node_from_free_list() {
+    fn __insert_node_as_head(&mut self, new_node_i: usize) {
+        self.main_memory[new_node_i].set_prev(None);
+        match self.head_and_tail {
             None => {
-                return Err(()); // full
+                self.main_memory[new_node_i].set_next(None);
+                self.head_and_tail = Some((new_node_i, new_node_i));
             }
-            Some(new_node_i) => {
-                
-                self.main_memory[new_node_i].element = Some(element);
-                
-                match self.head_and_tail {
-                    None => {
-                        if insertion_index != 0 {
-                            return Err(()); // out of bounds
-                        }
-                        self.head_and_tail = Some((new_node_i, new_node_i));
-                        self.main_memory[new_node_i].prev = None; // i think it will always be already None since we using first free index.
-                        self.main_memory[new_node_i].next = None;
-                    }
-                    Some((head, _tail)) => {
-                        
-                        if insertion_index == 0 {
-                            self.main_memory[new_node_i].next = Some(head);
-                            self.main_memory[new_node_i].prev = None;
-                            self.main_memory[head].prev = Some(new_node_i);
-                            self.head_and_tail.as_mut().unwrap().0 = new_node_i; // update the head since we are inserting at the begining
-                        
-                        } else {
-                            let mut current_node_i = head;
-                            for _ in 0..insertion_index-1 {
-                                match self.main_memory[current_node_i].next {
-                                    None => return Err(()), // out of bounds
-                                    Some(next_i) => {
-                                        current_node_i = next_i;
-                                    }
-                                }
-                            }
-                            
-                            self.__insert_node_after_node(new_node_i, current_node_i);
-                        }
-                    }
-                }
+            Some((head, _tail)) => {
+                self.main_memory[new_node_i].set_next(Some(head));
+                self.main_memory[head].set_prev(Some(new_node_i));
+                self.head_and_tail.as_mut().unwrap().0 = new_node_i;
             }
         }
+    }
+
+    fn __insert_node_as_tail(&mut self, new_node_i: usize) {
+        match self.head_and_tail {
+            None => self.__insert_node_as_head(new_node_i),
+            Some((_head, tail)) => self.__insert_node_after_node(new_node_i, tail),
+        }
+    }
+
+    // walks to the node at the virtual-index from whichever end of the list is closer.
+    // None if index out of bounds
+    fn __locate(&self, index: usize) -> Option<usize/*internal-array-index*/> {
+        if index >= self.len {
+            return None;
+        }
+        let (head, tail) = self.head_and_tail.unwrap(); // unwrap bc the list is not empty if index < len
+        if index < self.len / 2 {
+            let mut node_i = head;
+            for _ in 0..index {
+                node_i = self.main_memory[node_i].next().unwrap(); // unwrap bc we checked the index is within bounds
+            }
+            Some(node_i)
+        } else {
+            let mut node_i = tail;
+            for _ in 0..(self.len - 1 - index) {
+                node_i = self.main_memory[node_i].prev().unwrap(); // unwrap bc we checked the index is within bounds
+            }
+            Some(node_i)
+        }
+    }
+    
+    pub fn insert(&mut self, insertion_index: usize, element: T) -> Result<(), InsertError<T>> { // err if list is full or if index is out of bounds. the element comes back in the error
+        self.insert_with_handle(insertion_index, element).map(|_| ())
+    }
+    
+    pub fn insert_with_handle(&mut self, insertion_index: usize, element: T) -> Result<NodeHandle, InsertError<T>> { // err if list is full or if index is out of bounds. the element comes back in the error
+        // check everything before taking a node from the free list so that an error leaves the list as it was
+        if insertion_index > self.len {
+            return Err(IndexOutOfBounds::new(element, insertion_index, self.len).into());
+        }
+        let new_node_i = match self.__get_new_node_from_free_list() {
+            None => return Err(CapacityError::new(element).into()), // full
+            Some(new_node_i) => new_node_i,
+        };
+        self.__put_element(new_node_i, element);
+        if insertion_index == 0 {
+            self.__insert_node_as_head(new_node_i);
+        } else if insertion_index == self.len {
+            self.__insert_node_as_tail(new_node_i);
+        } else {
+            let current_node_i = self.__locate(insertion_index - 1).unwrap(); // unwrap bc we checked the index is within bounds
+            self.__insert_node_after_node(new_node_i, current_node_i);
+        }
         self.len += 1;
-        Ok(())
+        Ok(self.__handle_of_node(new_node_i))
     }
     
-    pub fn push(&mut self, element: T) -> Result<(), ()> { // err if list is full
-        self.insert(self.len, element) // later i can optimize the insert method to start from the tail if the index is closer to len than it is to 0.
+    pub fn push(&mut self, element: T) -> Result<(), CapacityError<T>> { // err if list is full
+        self.push_with_handle(element).map(|_| ())
     }
     
+    pub fn push_with_handle(&mut self, element: T) -> Result<NodeHandle, CapacityError<T>> { // err if list is full
+        self.insert_with_handle(self.len, element)
+            .map_err(|e| CapacityError::new(e.into_inner())) // index is always in bounds here so the only error is full
+    }
+
     fn __delete_node(&mut self, node_to_delete_i: usize) -> T {
-        match self.main_memory[node_to_delete_i].prev {
+        match self.main_memory[node_to_delete_i].prev() {
             Some(prev_i) => {
-                self.main_memory[prev_i].next = self.main_memory[node_to_delete_i].next;
+                let next_i = self.main_memory[node_to_delete_i].next();
+                self.main_memory[prev_i].set_next(next_i);
             }
             None => {
                 // node-to-delete is the head so we need to set a new head 
-                match self.main_memory[node_to_delete_i].next {
+                match self.main_memory[node_to_delete_i].next() {
                     Some(next_i) => {
                         self.head_and_tail.as_mut().unwrap().0 = next_i;
                     }
@@ -160,12 +299,13 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
                 }
             }
         }
-        match self.main_memory[node_to_delete_i].next {
+        match self.main_memory[node_to_delete_i].next() {
             Some(next_i) => {
-                self.main_memory[next_i].prev = self.main_memory[node_to_delete_i].prev;
+                let prev_i = self.main_memory[node_to_delete_i].prev();
+                self.main_memory[next_i].set_prev(prev_i);
             }
             None => { 
-                match self.main_memory[node_to_delete_i].prev {
+                match self.main_memory[node_to_delete_i].prev() {
                     Some(prev_i) => {
                         self.head_and_tail.as_mut().unwrap().1 = prev_i;
                     }
@@ -175,84 +315,177 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
                 }
             }
         }
-        self.main_memory[node_to_delete_i].next = self.free_list;
-        self.free_list = Some(node_to_delete_i);
+        self.__push_free_node(node_to_delete_i);
         self.len -= 1;
-        self.main_memory[node_to_delete_i].element.take().unwrap()
+        self.__take_element(node_to_delete_i).unwrap() // unwrap bc each node in the list is live
     }
     
-    // optimize to start from tail if len - insertion_index < len / 2
-    pub fn delete(&mut self, deletion_index: usize) -> Result<T, ()> { // error if index out of bounds 
-        match self.head_and_tail {
-            None => return Err(()), // nothing to delete
-            Some((head, _tail)) => {
-                let mut node_to_delete_i = head;
-                for _ in 0..deletion_index {
-                    node_to_delete_i = match self.main_memory[node_to_delete_i].next {
-                        None => return Err(()), // index out of bounds
-                        Some(i) => i,
-                    };
-                }
-                Ok(self.__delete_node(node_to_delete_i))
+    // the node must be unlinked from the list already
+    fn __push_free_node(&mut self, node_i: usize) {
+        let mut before = match self.free_list {
+            Some(first_free_i) if self.lowest_free_first && first_free_i < node_i => first_free_i,
+            _ => {
+                self.main_memory[node_i].set_next(self.free_list);
+                self.free_list = Some(node_i);
+                return;
+            }
+        };
+        // lowest-free-first: walk to the last free node below node_i so the free_list stays sorted
+        while let Some(next_free_i) = self.main_memory[before].next() {
+            if next_free_i > node_i {
+                break;
             }
+            before = next_free_i;
+        }
+        let after = self.main_memory[before].next();
+        self.main_memory[node_i].set_next(after);
+        self.main_memory[before].set_next(Some(node_i));
+    }
+
+    pub fn delete(&mut self, deletion_index: usize) -> Result<T, IndexOutOfBounds> { // error if index out of bounds 
+        match self.__locate(deletion_index) {
+            None => Err(IndexOutOfBounds::new((), deletion_index, self.len)),
+            Some(node_to_delete_i) => Ok(self.__delete_node(node_to_delete_i)),
         }
     }
     
-    // optimize to start from tail if len - insertion_index < len / 2
     pub fn get(&self, get_index: usize) -> Option<&T> { // none if index out of bounds
-        match self.head_and_tail {
-            None => return None, // nothing to get
-            Some((head, _tail)) => { // i can optimize this by starting from the tail if get_index > (len/2)
-                let mut node_to_get_i = head;
-                for _ in 0..get_index {
-                    node_to_get_i = match self.main_memory[node_to_get_i].next {
-                        None => return None, // index out of bounds
-                        Some(i) => i,
-                    };
-                }
-                Some(self.main_memory[node_to_get_i].element.as_ref().unwrap()) // unwrap is safe here because each element in the list is with a Some value
-            }
-        }
+        let node_to_get_i = self.__locate(get_index)?;
+        Some(self.__element(node_to_get_i).unwrap()) // unwrap is safe here because each node in the list is live
     }
     
-    // optimize to start from tail if len - insertion_index < len / 2
     pub fn get_mut(&mut self, get_index: usize) -> Option<&mut T> { // none if index out of bounds
-        match self.head_and_tail {
-            None => return None, // nothing to get
-            Some((head, _tail)) => { // i can optimize this by starting from the tail if get_index > (len/2)
-                let mut node_to_get_i = head;
-                for _ in 0..get_index {
-                    node_to_get_i = match self.main_memory[node_to_get_i].next {
-                        None => return None, // index out of bounds
-                        Some(i) => i,
-                    };
-                }
-                Some(self.main_memory[node_to_get_i].element.as_mut().unwrap()) // unwrap is safe here because each element in the list is with a Some value
-            }
-        }
+        let node_to_get_i = self.__locate(get_index)?;
+        Some(self.__element_mut(node_to_get_i).unwrap()) // unwrap is safe here because each node in the list is live
     }
     
-    // optimize to start from tail if len - insertion_index < len / 2
-    pub fn set(&mut self, set_index: usize, value: T) -> Result<T, ()> { // error if index out of bounds // returns old value
-        match self.head_and_tail {
-            None => return Err(()), // nothing to get
-            Some((head, _tail)) => { // i can optimize this by starting from the tail if get_index > (len/2)
-                let mut node_to_get_i = head;
-                for _ in 0..set_index {
-                    node_to_get_i = match self.main_memory[node_to_get_i].next {
-                        None => return Err(()), // index out of bounds
-                        Some(i) => i,
-                    };
-                }
-                Ok(self.main_memory[node_to_get_i].element.replace(value).unwrap()) // unwrap is safe here because each element in the list is with a Some value
-            }
+    pub fn set(&mut self, set_index: usize, value: T) -> Result<T, IndexOutOfBounds<T>> { // error if index out of bounds, the value comes back in the error // returns old value
+        match self.__locate(set_index) {
+            None => Err(IndexOutOfBounds::new(value, set_index, self.len)),
+            Some(node_to_set_i) => Ok(self.__replace_element(node_to_set_i, value).unwrap()), // unwrap is safe here because each node in the list is live
         }
     }
     
     pub fn len(&self) -> usize {
         self.len
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+
+    // deque. the ends are O(1), they use the head_and_tail directly.
+
+    pub fn push_back(&mut self, element: T) -> Result<(), CapacityError<T>> { // err if list is full
+        self.push(element)
+    }
+
+    pub fn push_front(&mut self, element: T) -> Result<(), CapacityError<T>> { // err if list is full
+        match self.__get_new_node_from_free_list() {
+            None => Err(CapacityError::new(element)), // full
+            Some(new_node_i) => {
+                self.__put_element(new_node_i, element);
+                self.__insert_node_as_head(new_node_i);
+                self.len += 1;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> { // none if the list is empty
+        let (head, _tail) = self.head_and_tail?;
+        Some(self.__delete_node(head))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> { // none if the list is empty
+        let (_head, tail) = self.head_and_tail?;
+        Some(self.__delete_node(tail))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        let (head, _tail) = self.head_and_tail?;
+        self.__element(head)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        let (_head, tail) = self.head_and_tail?;
+        self.__element(tail)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        let (head, _tail) = self.head_and_tail?;
+        self.__element_mut(head)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let (_head, tail) = self.head_and_tail?;
+        self.__element_mut(tail)
+    }
+
+    // drops the elements after the first len elements. does nothing if the list is not longer than len.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop_back();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+
+    // moving elements between lists. the lists can have different capacities and link index types.
+    // the elements get moved into the nodes of the other list so these are O(number of elements moved).
+    // on a capacity error nothing is moved.
+
+    // moves all the elements of other to the back of self, other is left empty.
+    pub fn append<const M: usize, Idx2: LinkIndex, L2: Layout>(&mut self, other: &mut StackStructure<T, M, Idx2, L2>) -> Result<(), CapacityError<()>> { // err if self doesn't have room for all of other
+        if N - self.len < other.len {
+            return Err(CapacityError::new(()));
+        }
+        while let Some(element) = other.pop_front() {
+            self.push_back(element).map_err(|_| ()).unwrap(); // unwrap bc we checked there is room
+        }
+        Ok(())
+    }
+
+    // moves all the elements of other to the front of self, in the same sequence. other is left empty.
+    pub fn prepend<const M: usize, Idx2: LinkIndex, L2: Layout>(&mut self, other: &mut StackStructure<T, M, Idx2, L2>) -> Result<(), CapacityError<()>> { // err if self doesn't have room for all of other
+        if N - self.len < other.len {
+            return Err(CapacityError::new(()));
+        }
+        while let Some(element) = other.pop_back() {
+            self.push_front(element).map_err(|_| ()).unwrap(); // unwrap bc we checked there is room
+        }
+        Ok(())
+    }
+
+    // moves the elements from index at to the end into a new list. self keeps the elements [0, at).
+    // panics if at > len.
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<StackStructure<T, M, Idx, L>, CapacityError<()>> { // err if the new list doesn't have room for the elements
+        if at > self.len {
+            core::panic!("split_off index (is {}) should be <= len (is {})", at, self.len);
+        }
+        if M < self.len - at {
+            return Err(CapacityError::new(()));
+        }
+        let mut tail_list = StackStructure::<T, M, Idx, L>::new();
+        while self.len > at {
+            tail_list.push_front(self.pop_back().unwrap()).map_err(|_| ()).unwrap(); // unwrap bc we checked there is room
+        }
+        Ok(tail_list)
+    }
         
+    #[allow(clippy::needless_return)]
     pub(crate) fn __binary_search_by_key<'a, K: Ord, F: Fn(&'a T)->K>(&'a self, key: K, key_of_the_element: F) 
     -> Result<(usize/*virtual-index*/, usize/*internal-array-index*/), (usize/*virtual-index*/, Option<usize>/*None means insert at virtual-index-~0, Some means the node that comes before a potential sorted insert*/)> // ok is the item is found at this location 
     {
@@ -274,10 +507,10 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
             
             let placement_difference: isize = (mid as isize) - (main_mem_ptr_index as isize);
             
-            let travel: &mut dyn FnMut(&Node<T>)->Option<usize> = if placement_difference >= 0 {
-                &mut |node: &Node<T>| { main_mem_ptr_index += 1; node.next }
+            let travel: &mut dyn FnMut(&Node<Idx>)->Option<usize> = if placement_difference >= 0 {
+                &mut |node: &Node<Idx>| { main_mem_ptr_index += 1; node.next() }
             } else {
-                &mut |node: &Node<T>| { main_mem_ptr_index -= 1; node.prev }
+                &mut |node: &Node<Idx>| { main_mem_ptr_index -= 1; node.prev() }
             };
             
             for _ in 0..placement_difference.abs() {
@@ -288,7 +521,7 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
            
             
             use core::cmp::Ordering;
-            match key_of_the_element(self.main_memory[main_mem_ptr].element.as_ref().unwrap()).cmp(&key) { // unwrap because traveling the list is with the lements.
+            match key_of_the_element(self.__element(main_mem_ptr).unwrap()).cmp(&key) { // unwrap because traveling the list is with the lements.
                 Ordering::Equal => {
                     //#[cfg(test)] println!("equal {:?}", ());
                     return Ok((mid, main_mem_ptr));
@@ -304,7 +537,7 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
                         None => return Err((0, None)),
                     };
                     if high < low {
-                        return Err((low, Some(self.main_memory[main_mem_ptr].prev.unwrap())));
+                        return Err((low, Some(self.main_memory[main_mem_ptr].prev().unwrap())));
                     }
                 }
             }
@@ -328,3 +561,43 @@ impl<T: Debug, const N: usize> StackStructure<T, N> {
     }
         
 }
+
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> Drop for StackStructure<T, N, Idx, L> {
+    fn drop(&mut self) {
+        if !core::mem::needs_drop::<T>() {
+            return;
+        }
+        // the nodes from fresh_nodes_start on were never used
+        for node_i in 0..self.fresh_nodes_start {
+            drop(self.__take_element(node_i));
+        }
+    }
+}
+
+// the elements of the free nodes show up as None
+impl<T: Debug, const N: usize, Idx: LinkIndex, L: Layout> Debug for StackStructure<T, N, Idx, L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct Elements<'a, T, const N: usize, Idx: LinkIndex, L: Layout>(&'a StackStructure<T, N, Idx, L>);
+        impl<'a, T: Debug, const N: usize, Idx: LinkIndex, L: Layout> Debug for Elements<'a, T, N, Idx, L> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries((0..N).map(|i| self.0.__element(i))).finish()
+            }
+        }
+        struct Links<'a, T, const N: usize, Idx: LinkIndex, L: Layout>(&'a StackStructure<T, N, Idx, L>);
+        impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> Debug for Links<'a, T, N, Idx, L> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries((0..N).map(|i| &self.0.main_memory[i])).finish()
+            }
+        }
+        f.debug_struct("StackStructure")
+            .field("elements", &Elements(self))
+            .field("main_memory", &Links(self))
+            .field("head_and_tail", &self.head_and_tail)
+            .field("free_list", &self.free_list)
+            .field("fresh_nodes_start", &self.fresh_nodes_start)
+            .field("len", &self.len)
+            .field("lowest_free_first", &self.lowest_free_first)
+            .finish()
+    }
+}
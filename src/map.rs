@@ -1,41 +1,52 @@
 use crate::StackStructure;
 use core::fmt::Debug;
+use core::iter::Map;
 use crate::iterators::{StackStructureIterator, StackStructureIteratorRef, StackStructureIteratorRefMut, StackStructureRChunks};
 use crate::simple::{StackSimple};
+use crate::error::CapacityError;
 
 // FOR THE MAP IN THE SEQUENCE // [ordered] map
 
 #[derive(Debug)]
-pub struct StackMap<K: Debug + Ord, V: Debug, const N: usize> {
+pub struct StackMap<K: Ord, V, const N: usize> {
     ss: StackStructure<(K, V), N>, // keep private
 }
-impl<K: Debug + Ord, V: Debug, const N: usize> StackMap<K, V, N> {
-    pub fn new() -> Self {
+impl<K: Ord, V, const N: usize> StackMap<K, V, N> {
+    pub const fn new() -> Self {
         Self {
             ss: StackStructure::new(),
         }
     }
-    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, ()> { // error if ss is full // some if the value existed there previously {
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> { // error if ss is full, the key and value come back in the error // some if the value existed there previously {
         // keep the ss in the sort sequence, that is how we find things.
         match self.ss.__binary_search_by_key(&key, |t| { &t.0 }) {
             Ok((_virtual_i, internal_array_i)) => {
                 // item is found at this location, set element
                 //#[cfg(test)] std::println!("ok {:?}, {:?}", virtual_i, internal_array_i);
-                Ok(Some(self.ss.main_memory[internal_array_i].element.replace((key, value)).unwrap().1)) // unwrap safe bc the binary search returned ok with this location
+                Ok(Some(self.ss.__replace_element(internal_array_i, (key, value)).unwrap().1)) // unwrap safe bc the binary search returned ok with this location
             }
             Err((_virtual_i, None)) => {
                 //#[cfg(test)] std::println!("err {:?}, None", virtual_i,);
-                self.ss.insert(0, (key, value))
-                    .map(|()| None)
+                match self.ss.__get_new_node_from_free_list() {
+                    None => {
+                        Err(CapacityError::new((key, value)))
+                    }
+                    Some(new_node_i) => {
+                        self.ss.__put_element(new_node_i, (key, value));
+                        self.ss.__insert_node_as_head(new_node_i);
+                        self.ss.len += 1;
+                        Ok(None)
+                    }
+                }
             }
             Err((_virtual_i, Some(node_before))) => {
                 //#[cfg(test)] std::println!("err {:?}, {:?}", virtual_i, node_before);
                 match self.ss.__get_new_node_from_free_list() {
                     None => {
-                        return Err(()); 
+                        Err(CapacityError::new((key, value)))
                     }
                     Some(new_node_i) => {
-                        self.ss.main_memory[new_node_i].element = Some((key, value));
+                        self.ss.__put_element(new_node_i, (key, value));
                         self.ss.__insert_node_after_node(new_node_i, node_before);
                         self.ss.len += 1;
                         Ok(None)
@@ -47,7 +58,7 @@ impl<K: Debug + Ord, V: Debug, const N: usize> StackMap<K, V, N> {
     pub fn get(&self, key: &K) -> Option<&V> {  
         match self.ss.__binary_search_by_key(key, |t| &t.0) {
             Ok((_virtual_i, internal_array_index)) => {
-                Some(&self.ss.main_memory[internal_array_index].element.as_ref().unwrap().1) // unwrap safe bc binary-search returned Ok  
+                Some(&self.ss.__element(internal_array_index).unwrap().1) // unwrap safe bc binary-search returned Ok  
             }
             Err(_) => None,
         }
@@ -55,7 +66,7 @@ impl<K: Debug + Ord, V: Debug, const N: usize> StackMap<K, V, N> {
     pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {  
         match self.ss.__binary_search_by_key(key, |t| &t.0) {
             Ok((_virtual_i, internal_array_index)) => {
-                Some(&mut self.ss.main_memory[internal_array_index].element.as_mut().unwrap().1) // unwrap safe bc binary-search returned Ok  
+                Some(&mut self.ss.__element_mut(internal_array_index).unwrap().1) // unwrap safe bc binary-search returned Ok  
             }
             Err(_) => None,
         }
@@ -73,39 +84,49 @@ impl<K: Debug + Ord, V: Debug, const N: usize> StackMap<K, V, N> {
     pub fn len(&self) -> usize {
         self.ss.len
     }
+    pub fn is_empty(&self) -> bool {
+        self.ss.len == 0
+    }
     
-    pub fn iter<'a>(&'a self) -> core::iter::Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&'a (K, V))->(&'a K, &'a V)> {
-        fn i<'a, A, B>(t: &'a (A, B)) -> (&'a A, &'a B) { (&t.0, &t.1) }
-        self.ss.iter().map(i as fn(&'a (K, V))->(&'a K, &'a V))
+    pub fn iter(&self) -> StackMapIter<'_, K, V, N> {
+        fn i<A, B>(t: &(A, B)) -> (&A, &B) { (&t.0, &t.1) }
+        self.ss.iter().map(i as fn(&(K, V)) -> (&K, &V))
     }
-    pub fn iter_mut<'a>(&'a mut self) -> core::iter::Map<StackStructureIteratorRefMut<'a, (K, V), N>, fn(&'a mut (K, V))->(&'a K, &'a mut V)> {
-        fn im<'a, A, B>(t: &'a mut (A, B)) -> (&'a A, &'a mut B) { (&t.0, &mut t.1) }
-        self.ss.iter_mut().map(im as fn(&'a mut (K, V))->(&'a K, &'a mut V))
+    pub fn iter_mut(&mut self) -> StackMapIterMut<'_, K, V, N> {
+        fn im<A, B>(t: &mut (A, B)) -> (&A, &mut B) { (&t.0, &mut t.1) }
+        self.ss.iter_mut().map(im as fn(&mut (K, V)) -> (&K, &mut V))
     }
-    pub fn keys<'a>(&'a self) -> core::iter::Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&'a (K, V))->&'a K> {
-        fn k<'a, A, B>(t: &'a (A, B)) -> &'a A { &t.0 }
-        self.ss.iter().map(k as fn(&'a (K, V)) -> &'a K)
+    pub fn keys(&self) -> StackMapKeys<'_, K, V, N> {
+        fn k<A, B>(t: &(A, B)) -> &A { &t.0 }
+        self.ss.iter().map(k as fn(&(K, V)) -> &K)
     }
-    pub fn values<'a>(&'a self) -> core::iter::Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&'a (K, V))->&'a V> {
-        fn v<'a, A, B>(t: &'a (A, B)) -> &'a B { &t.1 }
-        self.ss.iter().map(v as fn(&'a (K, V)) -> &'a V)
+    pub fn values(&self) -> StackMapValues<'_, K, V, N> {
+        fn v<A, B>(t: &(A, B)) -> &B { &t.1 }
+        self.ss.iter().map(v as fn(&(K, V)) -> &V)
     }
-    pub fn values_mut<'a>(&'a mut self) -> core::iter::Map<StackStructureIteratorRefMut<'a, (K, V), N>, fn(&'a mut (K, V))->&'a mut V> {
-        fn vm<'a, A, B>(t: &'a mut (A, B)) -> &'a mut B { &mut t.1 }
-        self.ss.iter_mut().map(vm as fn(&'a mut (K, V)) -> &'a mut V)
+    pub fn values_mut(&mut self) -> StackMapValuesMut<'_, K, V, N> {
+        fn vm<A, B>(t: &mut (A, B)) -> &mut B { &mut t.1 }
+        self.ss.iter_mut().map(vm as fn(&mut (K, V)) -> &mut V)
     }
-    pub fn rchunks<'a, const C: usize>(&'a self) -> core::iter::Map<StackStructureRChunks<'a, (K, V), N, C>, fn(StackSimple<&'a (K, V), C>)->StackSimple<(&'a K, &'a V), C>> {  
-        fn i<'a, A, B, const C: usize>(ss: StackSimple<&'a (A, B), C>) -> StackSimple<(&'a A, &'a B), C> { 
+    pub fn rchunks<const C: usize>(&self) -> StackMapRChunks<'_, K, V, N, C> {
+        fn i<A, B, const C: usize>(ss: StackSimple<&(A, B), C>) -> StackSimple<(&A, &B), C> {
             ss.into_iter().map(|t| (&t.0, &t.1)).collect()
         }
-        self.ss.rchunks::<C>().map(i as fn(StackSimple<&'a (K, V), C>)->StackSimple<(&'a K, &'a V), C>)        
-    }   
-  
+        self.ss.rchunks::<C>().map(i as fn(StackSimple<&(K, V), C>) -> StackSimple<(&K, &V), C>)
+    }
 }
 
 
 
-impl<K: Ord + Debug, V: Debug, const N: usize> FromIterator<(K, V)> for StackMap<K, V, N> {
+// the iterators of StackMap. the fn pointer types are spelled out so the iterator types can be named
+pub type StackMapIter<'a, K, V, const N: usize> = Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&(K, V)) -> (&K, &V)>;
+pub type StackMapIterMut<'a, K, V, const N: usize> = Map<StackStructureIteratorRefMut<'a, (K, V), N>, fn(&mut (K, V)) -> (&K, &mut V)>;
+pub type StackMapKeys<'a, K, V, const N: usize> = Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&(K, V)) -> &K>;
+pub type StackMapValues<'a, K, V, const N: usize> = Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&(K, V)) -> &V>;
+pub type StackMapValuesMut<'a, K, V, const N: usize> = Map<StackStructureIteratorRefMut<'a, (K, V), N>, fn(&mut (K, V)) -> &mut V>;
+pub type StackMapRChunks<'a, K, V, const N: usize, const C: usize> = Map<StackStructureRChunks<'a, (K, V), N, C>, fn(StackSimple<&(K, V), C>) -> StackSimple<(&K, &V), C>>;
+
+impl<K: Ord, V, const N: usize> FromIterator<(K, V)> for StackMap<K, V, N> {
     fn from_iter<Iter: IntoIterator<Item=(K, V)>>(iter: Iter) -> Self {
         // must sort them.
         let mut map = Self::new();
@@ -116,10 +137,68 @@ impl<K: Ord + Debug, V: Debug, const N: usize> FromIterator<(K, V)> for StackMap
     }    
 }
 
-impl<K: Ord + Debug, V: Debug, const N: usize> IntoIterator for StackMap<K, V, N> {
+impl<K: Ord, V, const N: usize> IntoIterator for StackMap<K, V, N> {
     type Item = (K, V);
     type IntoIter = StackStructureIterator<(K, V), N>;
     fn into_iter(self) -> Self::IntoIter {
         self.ss.into_iter()
     }
 }
+
+
+// std traits. they go by the (key, value) pairs in key order. the comparisons work across capacities.
+
+impl<K: Ord + Clone, V: Clone, const N: usize> Clone for StackMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            ss: self.ss.clone(),
+        }
+    }
+}
+impl<K: Ord, V, const N: usize> Default for StackMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<K: Ord, V: PartialEq, const N: usize, const M: usize> PartialEq<StackMap<K, V, M>> for StackMap<K, V, N> {
+    fn eq(&self, other: &StackMap<K, V, M>) -> bool {
+        self.ss == other.ss
+    }
+}
+impl<K: Ord, V: Eq, const N: usize> Eq for StackMap<K, V, N> {}
+impl<K: Ord, V: PartialOrd, const N: usize, const M: usize> PartialOrd<StackMap<K, V, M>> for StackMap<K, V, N> {
+    fn partial_cmp(&self, other: &StackMap<K, V, M>) -> Option<core::cmp::Ordering> {
+        self.ss.partial_cmp(&other.ss)
+    }
+}
+impl<K: Ord, V: Ord, const N: usize> Ord for StackMap<K, V, N> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.ss.cmp(&other.ss)
+    }
+}
+impl<K: Ord + core::hash::Hash, V: core::hash::Hash, const N: usize> core::hash::Hash for StackMap<K, V, N> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.ss.hash(state)
+    }
+}
+impl<'a, K: Ord, V, const N: usize> core::ops::Index<&'a K> for StackMap<K, V, N> {
+    type Output = V;
+    // panics if the key is not in the map
+    fn index(&self, key: &'a K) -> &Self::Output {
+        self.get(key).expect("key not found in the map")
+    }
+}
+impl<'a, K: Ord, V, const N: usize> IntoIterator for &'a StackMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = core::iter::Map<StackStructureIteratorRef<'a, (K, V), N>, fn(&'a (K, V))->(&'a K, &'a V)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, K: Ord, V, const N: usize> IntoIterator for &'a mut StackMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = core::iter::Map<StackStructureIteratorRefMut<'a, (K, V), N>, fn(&'a mut (K, V))->(&'a K, &'a mut V)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
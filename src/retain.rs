@@ -0,0 +1,85 @@
+use super::*;
+
+// single-pass removal by predicate. the removed nodes are unlinked with __delete_node as we go, so they go straight back to the free_list.
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+
+    // keeps only the elements for which f returns true. visits each element once, in sequence.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|element| f(element));
+    }
+
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut current_node_i: Option<usize> = self.head_and_tail.map(|(head, _tail)| head);
+        while let Some(node_i) = current_node_i {
+            current_node_i = self.main_memory[node_i].next(); // grab it before the node goes to the free list
+            if !f(self.__element_mut(node_i).unwrap()) {
+                self.__delete_node(node_i);
+            }
+        }
+    }
+
+    // removes consecutive equal elements, keeps the first one of each run.
+    pub fn dedup(&mut self)
+    where T: PartialEq {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // same_bucket is called with (the current element, the last kept element before it). if it returns true the current element is removed.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let mut kept_node_i: usize = match self.head_and_tail {
+            None => return, // empty
+            Some((head, _tail)) => head,
+        };
+        let mut current_node_i: Option<usize> = self.main_memory[kept_node_i].next();
+        while let Some(node_i) = current_node_i {
+            current_node_i = self.main_memory[node_i].next(); // grab it before the node goes to the free list
+            let [current, kept] = self.main_memory.elements_disjoint_mut(node_i, kept_node_i); // two different nodes in the list
+            let (current, kept) = unsafe { (current.assume_init_mut(), kept.assume_init_mut()) }; // safe bc both nodes are in the list so they are live
+            if same_bucket(current, kept) {
+                self.__delete_node(node_i);
+            } else {
+                kept_node_i = node_i;
+            }
+        }
+    }
+
+    // lazy. each call to next walks forward to the next element for which filter returns true, removes it and gives it out.
+    // the elements that are not reached before the iterator is dropped stay in the list.
+    pub fn extract_if<'a, F: FnMut(&mut T) -> bool>(&'a mut self, filter: F) -> StackStructureExtractIf<'a, T, N, F, Idx, L> {
+        StackStructureExtractIf{
+            current_node_i: self.head_and_tail.map(|(head, _tail)| head),
+            ms: self,
+            filter,
+        }
+    }
+}
+
+
+pub struct StackStructureExtractIf<'a, T, const N: usize, F: FnMut(&mut T) -> bool, Idx: LinkIndex = usize, L: Layout = Split> {
+    ms: &'a mut StackStructure<T, N, Idx, L>,
+    current_node_i: Option<usize>, // none if there are no more items to look at
+    filter: F,
+}
+
+impl<'a, T, const N: usize, F: FnMut(&mut T) -> bool, Idx: LinkIndex, L: Layout> Iterator for StackStructureExtractIf<'a, T, N, F, Idx, L> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node_i) = self.current_node_i {
+            self.current_node_i = self.ms.main_memory[node_i].next(); // grab it before the node goes to the free list
+            if (self.filter)(self.ms.__element_mut(node_i).unwrap()) {
+                return Some(self.ms.__delete_node(node_i));
+            }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.ms.len()))
+    }
+}
+
+impl<'a, T, const N: usize, F: FnMut(&mut T) -> bool, Idx: LinkIndex, L: Layout> core::iter::FusedIterator for StackStructureExtractIf<'a, T, N, F, Idx, L> {}
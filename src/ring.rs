@@ -0,0 +1,307 @@
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::iter::{Chain, FusedIterator, ExactSizeIterator};
+use crate::error::CapacityError;
+use crate::simple::{slice_assume_init_ref, slice_assume_init_mut};
+
+// FOR THE QUEUES // fixed capacity ring buffer on the stack, the same [MaybeUninit<T>; C] as StackSimple but without the links of StackStructure
+// push and pop at both ends in O(1). the elements wrap around the end of the data, so they are one or two contiguous slices.
+// the _overwriting pushes drop the oldest element out of the other end when full, for sample buffers that keep the last C values.
+
+pub struct StackRing<T, const C: usize> {
+    data: [MaybeUninit<T>; C], // private field portant // the items in [head, head + len) wrapping around C are initialized, the rest is not
+    head: usize, // the physical index of the front element. < C, or 0 when C is 0
+    len: usize,
+}
+
+impl<T, const C: usize> StackRing<T, C> {
+    pub const fn new() -> Self {
+        Self {
+            data: [const { MaybeUninit::uninit() }; C],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn is_full(&self) -> bool {
+        self.len == C
+    }
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+
+    // the physical index of the element at virtual-index i. i <= C, so one wrap is enough and C = 0 never divides
+    fn __physical(&self, i: usize) -> usize {
+        let p = self.head + i;
+        if p >= C { p - C } else { p }
+    }
+
+    pub fn push_back(&mut self, value: T) -> Result<(), CapacityError<T>> { // err if full. the value comes back in the error
+        if self.len == C {
+            return Err(CapacityError::new(value));
+        }
+        let i = self.__physical(self.len);
+        self.data[i].write(value);
+        self.len += 1;
+        Ok(())
+    }
+    pub fn push_front(&mut self, value: T) -> Result<(), CapacityError<T>> { // err if full. the value comes back in the error
+        if self.len == C {
+            return Err(CapacityError::new(value));
+        }
+        self.head = if self.head == 0 { C - 1 } else { self.head - 1 }; // C > 0 bc not full
+        self.data[self.head].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> { // none if empty
+        if self.len == 0 {
+            return None;
+        }
+        let value = unsafe { self.data[self.head].assume_init_read() }; // unsafe ok bc the front is initialized when len > 0
+        self.head = self.__physical(1);
+        self.len -= 1;
+        Some(value)
+    }
+    pub fn pop_back(&mut self) -> Option<T> { // none if empty
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let i = self.__physical(self.len);
+        Some(unsafe { self.data[i].assume_init_read() }) // unsafe ok bc it was the back element
+    }
+
+    // pushes at the back, and if full pops the front to make room. some with the popped front.
+    // when C is 0 nothing fits, the value itself comes back.
+    pub fn push_back_overwriting(&mut self, value: T) -> Option<T> {
+        if C == 0 {
+            return Some(value);
+        }
+        let oldest = if self.is_full() { self.pop_front() } else { None };
+        self.push_back(value).unwrap(); // unwrap bc there is room now
+        oldest
+    }
+    // pushes at the front, and if full pops the back to make room. some with the popped back.
+    pub fn push_front_overwriting(&mut self, value: T) -> Option<T> {
+        if C == 0 {
+            return Some(value);
+        }
+        let oldest = if self.is_full() { self.pop_back() } else { None };
+        self.push_front(value).unwrap(); // unwrap bc there is room now
+        oldest
+    }
+
+    // virtual-index 0 is the front
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        Some(unsafe { self.data[self.__physical(index)].assume_init_ref() }) // unsafe ok bc index < len
+    }
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let i = self.__physical(index);
+        Some(unsafe { self.data[i].assume_init_mut() }) // unsafe ok bc index < len
+    }
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.checked_sub(1)?)
+    }
+
+    // the elements in order as two slices, the front part and then the wrapped around part. the second is empty if it doesn't wrap.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = if self.head + self.len <= C {
+            (&self.data[self.head..self.head + self.len], &self.data[..0])
+        } else {
+            (&self.data[self.head..], &self.data[..self.head + self.len - C])
+        };
+        unsafe { (slice_assume_init_ref(front), slice_assume_init_ref(back)) } // unsafe ok bc these are the initialized items
+    }
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let wrapped = (self.head + self.len).saturating_sub(C);
+        let end = (self.head + self.len).min(C);
+        let (before_head, from_head) = self.data.split_at_mut(self.head);
+        let (front, back) = (&mut from_head[..end - self.head], &mut before_head[..wrapped]);
+        unsafe { (slice_assume_init_mut(front), slice_assume_init_mut(back)) } // unsafe ok bc these are the initialized items
+    }
+    // true if the elements are one slice, i.e. the second of as_slices is empty
+    pub fn is_contiguous(&self) -> bool {
+        self.head + self.len <= C
+    }
+    // rotates the data so the front element is at data[0], then the elements are one slice. O(C), no extra memory.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.data.rotate_left(self.head);
+        self.head = 0;
+        self.as_mut_slices().0
+    }
+
+    pub fn iter(&self) -> Chain<core::slice::Iter<'_, T>, core::slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back.iter())
+    }
+    pub fn iter_mut(&mut self) -> Chain<core::slice::IterMut<'_, T>, core::slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back.iter_mut())
+    }
+
+    pub fn clear(&mut self) {
+        // drops the back part when it goes out of scope, so a panic in a drop of the front part doesn't leak the back part. same as VecDeque
+        struct BackDropper<T>(*mut [T]);
+        impl<T> Drop for BackDropper<T> {
+            fn drop(&mut self) {
+                unsafe { core::ptr::drop_in_place(self.0) } // unsafe ok bc clear made the ring empty before, nothing else drops these
+            }
+        }
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        self.head = 0;
+        self.len = 0; // first, so a panic in one of the drops can't get them dropped again
+        let _back_dropper = BackDropper(back);
+        unsafe { core::ptr::drop_in_place(front) } // unsafe ok bc these are the initialized items
+    }
+}
+
+// drops exactly the len items from head on, the rest of the data is uninitialized
+impl<T, const C: usize> Drop for StackRing<T, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// index traits. virtual-index 0 is the front. panics if index >= len, same as VecDeque.
+
+impl<T, const C: usize> core::ops::Index<usize> for StackRing<T, C> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+impl<T, const C: usize> core::ops::IndexMut<usize> for StackRing<T, C> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => panic!("index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+// into-iterator. pops from the front, or the back for next_back. the ones that are not given out get dropped with the ring.
+
+pub struct StackRingIterator<T, const C: usize> {
+    ring: StackRing<T, C>,
+}
+impl<T, const C: usize> Iterator for StackRingIterator<T, C> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.ring.pop_front()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.ring.len, Some(self.ring.len))
+    }
+}
+impl<T, const C: usize> DoubleEndedIterator for StackRingIterator<T, C> {
+    fn next_back(&mut self) -> Option<T> {
+        self.ring.pop_back()
+    }
+}
+impl<T, const C: usize> ExactSizeIterator for StackRingIterator<T, C> {}
+impl<T, const C: usize> FusedIterator     for StackRingIterator<T, C> {}
+
+impl<T, const C: usize> IntoIterator for StackRing<T, C> {
+    type Item = T;
+    type IntoIter = StackRingIterator<T, C>;
+    fn into_iter(self) -> Self::IntoIter {
+        StackRingIterator{
+            ring: self,
+        }
+    }
+}
+impl<'a, T, const C: usize> IntoIterator for &'a StackRing<T, C> {
+    type Item = &'a T;
+    type IntoIter = Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const C: usize> IntoIterator for &'a mut StackRing<T, C> {
+    type Item = &'a mut T;
+    type IntoIter = Chain<core::slice::IterMut<'a, T>, core::slice::IterMut<'a, T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const C: usize> FromIterator<T> for StackRing<T, C> {
+    fn from_iter<Iter: IntoIterator<Item=T>>(iter: Iter) -> Self {
+        let mut ring = Self::new();
+        ring.extend(iter);
+        ring
+    }
+}
+// pushes at the back
+impl<T, const C: usize> Extend<T> for StackRing<T, C> {
+    fn extend<Iter: IntoIterator<Item=T>>(&mut self, iter: Iter) {
+        for item in iter {
+            self.push_back(item).unwrap(); // will panic if not enough room!
+        }
+    }
+}
+
+
+// std traits. these all go by the elements in order, not by where they are in the data. the comparisons work across capacities.
+
+impl<T: Clone, const C: usize> Clone for StackRing<T, C> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+impl<T, const C: usize> Default for StackRing<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: fmt::Debug, const C: usize> fmt::Debug for StackRing<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T: PartialEq<U>, U, const C: usize, const D: usize> PartialEq<StackRing<U, D>> for StackRing<T, C> {
+    fn eq(&self, other: &StackRing<U, D>) -> bool {
+        self.len == other.len && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+impl<T: PartialEq<U>, U, const C: usize, const D: usize> PartialEq<[U; D]> for StackRing<T, C> {
+    fn eq(&self, other: &[U; D]) -> bool {
+        self.len == D && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+impl<T: Eq, const C: usize> Eq for StackRing<T, C> {}
+impl<T: core::hash::Hash, const C: usize> core::hash::Hash for StackRing<T, C> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        self.iter().for_each(|element| element.hash(state));
+    }
+}
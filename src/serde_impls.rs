@@ -0,0 +1,113 @@
+use super::*;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+// behind the serde feature. the lists go as sequences in list order, the map goes as a map in key order.
+// deserializing input with more elements than the capacity is an invalid_length error, it never panics like FromIterator.
+
+impl<T: Serialize, const N: usize, Idx: LinkIndex, L: Layout> Serialize for StackStructure<T, N, Idx, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<T: Serialize, const C: usize> Serialize for StackSimple<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<K: Ord + Serialize, V: Serialize, const N: usize> Serialize for StackMap<K, V, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+
+struct StackStructureVisitor<T, const N: usize, Idx, L>(PhantomData<(T, Idx, L)>);
+
+impl<'de, T: Deserialize<'de>, const N: usize, Idx: LinkIndex, L: Layout> Visitor<'de> for StackStructureVisitor<T, N, Idx, L> {
+    type Value = StackStructure<T, N, Idx, L>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements", N)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        if let Some(len) = seq.size_hint() {
+            if len > N {
+                return Err(A::Error::invalid_length(len, &self));
+            }
+        }
+        let mut ms = StackStructure::new();
+        while let Some(element) = seq.next_element()? {
+            if ms.push(element).is_err() {
+                return Err(A::Error::invalid_length(N + 1, &self));
+            }
+        }
+        Ok(ms)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const N: usize, Idx: LinkIndex, L: Layout> Deserialize<'de> for StackStructure<T, N, Idx, L> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(StackStructureVisitor(PhantomData))
+    }
+}
+
+
+struct StackSimpleVisitor<T, const C: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const C: usize> Visitor<'de> for StackSimpleVisitor<T, C> {
+    type Value = StackSimple<T, C>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements", C)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        if let Some(len) = seq.size_hint() {
+            if len > C {
+                return Err(A::Error::invalid_length(len, &self));
+            }
+        }
+        let mut ss = StackSimple::new();
+        while let Some(element) = seq.next_element()? {
+            if ss.push(element).is_err() {
+                return Err(A::Error::invalid_length(C + 1, &self));
+            }
+        }
+        Ok(ss)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const C: usize> Deserialize<'de> for StackSimple<T, C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(StackSimpleVisitor(PhantomData))
+    }
+}
+
+
+struct StackMapVisitor<K, V, const N: usize>(PhantomData<(K, V)>);
+
+impl<'de, K: Ord + Deserialize<'de>, V: Deserialize<'de>, const N: usize> Visitor<'de> for StackMapVisitor<K, V, N> {
+    type Value = StackMap<K, V, N>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map of at most {} entries", N)
+    }
+    // the keys can come in any order, insert keeps them sorted. on a duplicate key the last value wins.
+    // so the size_hint can't be checked up front, the duplicates don't take room.
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = StackMap::new();
+        while let Some((key, value)) = access.next_entry()? {
+            if map.insert(key, value).is_err() {
+                return Err(A::Error::invalid_length(N + 1, &self));
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<'de, K: Ord + Deserialize<'de>, V: Deserialize<'de>, const N: usize> Deserialize<'de> for StackMap<K, V, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(StackMapVisitor(PhantomData))
+    }
+}
@@ -2,8 +2,9 @@ use core::ops::*;
 use core::slice::SliceIndex;
 use core::mem::MaybeUninit;
 use core::iter::{FusedIterator, ExactSizeIterator};
+use crate::error::{CapacityError, IndexOutOfBounds, InsertError};
 
-// use the slice-index syntax to view this StackSimple &StackSimple[..] outputs &[T]
+// use the slice-index syntax to view this StackSimple &StackSimple[..] outputs &[T]. it also derefs to the &[T]
 // C is the max-size and capacity of the StackSimple. len is the current len of the valid-items in the StackSimple.
 // this struct takes the place of when need an owned variable sized immutable array on the stack
 // using this struct, an array is created on the stack with the max-length and the unused slots are hidden
@@ -15,19 +16,24 @@ pub struct StackSimple<T, const C: usize> {
 }
 
 impl<T, const C: usize> StackSimple<T, C> {
-    pub fn new() -> Self { 
+    pub const fn new() -> Self { 
         Self {
             data: [const { MaybeUninit::uninit() }; C],
             len: 0,
         }
     }
-    // if you want the stack-simple to have bigger capacity then the length of the given array, then use StackSimple::<T, C/*set capacity here*/>::from_iter(array) but make sure capacity is greater than the length of the array.
-    pub fn from_array_of_same_capacity(a: [T; C]) -> Self {
+    // if you want the stack-simple to have bigger capacity then the length of the given array, then use StackSimple::<T, C/*set capacity here*/>::from(array). it checks at compile time that the array fits.
+    pub const fn from_array_of_same_capacity(a: [T; C]) -> Self {
+        Self::from_array(a)
+    }
+    // const so it works for statics
+    pub const fn from_array(a: [T; C]) -> Self {
         Self {
-            len: a.len(),
+            len: C,
             data: array_as_maybeuninit(a),            
         }
     }
+    /// # Safety
     /// caller must make sure that the first len number of items is initialized with maybeuninit.write and caller must make sure that the remaining elements are not initialized yet.
     /// len must be <= data.len()
     pub unsafe fn from_maybeuninit_data_and_len(data: [MaybeUninit<T>; C], len: usize) -> Self {
@@ -39,9 +45,9 @@ impl<T, const C: usize> StackSimple<T, C> {
     pub fn len(&self) -> usize {
         self.len
     }
-    pub fn push(&mut self, value: T) -> Result<(), ()> { // err if full
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> { // err if full. the value comes back in the error
         if self.len == C {
-            return Err(());
+            return Err(CapacityError::new(value));
         }
         self.data[self.len].write(value);
         self.len += 1;
@@ -59,21 +65,25 @@ impl<T, const C: usize> StackSimple<T, C> {
         self.len -= 1;
         Some(unsafe { y.assume_init() }) //unsafe ok bc the len tells us where the valid values are. 
     }
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index >= self.len {
-            return None;
-        }
-        Some(unsafe { &*self.data[index].as_ptr() }) // unsafe ok bc we checked the index is within range
+    // takes the same indexes as the get of a slice, a usize or a range
+    pub fn get<I: SliceIndex<[T]>>(&self, index: I) -> Option<&I::Output> {
+        self.as_slice().get(index)
     }
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        if index >= self.len {
-            return None;
-        }
-        Some(unsafe { &mut *self.data[index].as_mut_ptr() }) // unsafe ok bc we checked the index is within range
+    pub fn get_mut<I: SliceIndex<[T]>>(&mut self, index: I) -> Option<&mut I::Output> {
+        self.as_mut_slice().get_mut(index)
+    }
+    // the valid items. also what Deref gives
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) } // unsafe ok bc the first len items are initialized
+    }
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len) } // unsafe ok bc the first len items are initialized
     }
+    #[allow(clippy::into_iter_on_ref)]
     pub fn iter(&self) -> core::slice::Iter<'_, T> {
         (&self[..]).into_iter()
     }
+    #[allow(clippy::into_iter_on_ref)]
     pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
         (&mut self[..]).into_iter()
     }
@@ -81,6 +91,176 @@ impl<T, const C: usize> StackSimple<T, C> {
     pub fn rchunks(&self, chunk_size: usize) -> StackSimpleRChunks<'_, T, C> {
         StackSimpleRChunks::new(self, chunk_size)
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn is_full(&self) -> bool {
+        self.len == C
+    }
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+
+
+    // vec-like mutation. the elements are moved with ptr copies inside the data, the slots past len are uninitialized after every call.
+
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> { // err if full or if index > len. the element comes back in the error
+        if index > self.len {
+            return Err(IndexOutOfBounds::new(element, index, self.len).into());
+        }
+        if self.len == C {
+            return Err(CapacityError::new(element).into());
+        }
+        unsafe {
+            let p = self.data.as_mut_ptr().add(index);
+            core::ptr::copy(p, p.add(1), self.len - index); // shift the tail one to the right, the slot at len is free bc not full
+        }
+        self.data[index].write(element);
+        self.len += 1;
+        Ok(())
+    }
+    // shifts the elements after index to the left, O(len - index)
+    pub fn remove(&mut self, index: usize) -> Result<T, IndexOutOfBounds> { // err if index out of bounds
+        if index >= self.len {
+            return Err(IndexOutOfBounds::new((), index, self.len));
+        }
+        unsafe {
+            let p = self.data.as_mut_ptr().add(index);
+            let element = p.read().assume_init(); // unsafe ok bc index < len
+            core::ptr::copy(p.add(1), p, self.len - index - 1);
+            self.len -= 1;
+            Ok(element)
+        }
+    }
+    // the last element takes the place of the removed one, O(1)
+    pub fn swap_remove(&mut self, index: usize) -> Result<T, IndexOutOfBounds> { // err if index out of bounds
+        if index >= self.len {
+            return Err(IndexOutOfBounds::new((), index, self.len));
+        }
+        let last = self.pop().unwrap(); // unwrap bc index < len so not empty
+        if index == self.len {
+            return Ok(last);
+        }
+        Ok(core::mem::replace(unsafe { self.data[index].assume_init_mut() }, last)) // unsafe ok bc index < len
+    }
+    // drops the elements after the first len elements. does nothing if the list is not longer than len.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let old_len = self.len;
+        self.len = len; // first, so a panic in one of the drops can't get them dropped again
+        unsafe { core::ptr::drop_in_place(slice_assume_init_mut(&mut self.data[len..old_len])) }
+    }
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    // keeps only the elements for which f returns true, in sequence. O(len)
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|element| f(element));
+    }
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut g = CompactGuard::new(self);
+        while g.processed < g.original_len {
+            let p = g.s.data[g.processed].as_mut_ptr();
+            if f(unsafe { &mut *p }) { // unsafe ok bc processed < original_len
+                g.keep();
+            } else {
+                g.processed += 1; // first, so a panic in the drop doesn't drop it again
+                unsafe { core::ptr::drop_in_place(p) }
+            }
+        }
+    }
+
+    // removes consecutive equal elements, keeps the first one of each run.
+    pub fn dedup(&mut self)
+    where T: PartialEq {
+        self.dedup_by(|a, b| a == b);
+    }
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+    // same_bucket is called with (the current element, the last kept element before it). if it returns true the current element is removed.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        if self.len <= 1 {
+            return;
+        }
+        let mut g = CompactGuard::new(self);
+        g.keep(); // the first one always stays
+        while g.processed < g.original_len {
+            let p = g.s.data[g.processed].as_mut_ptr();
+            let kept = g.s.data[g.kept - 1].as_mut_ptr(); // a different slot bc kept <= processed
+            if same_bucket(unsafe { &mut *p }, unsafe { &mut *kept }) { // unsafe ok bc both are initialized
+                g.processed += 1; // first, so a panic in the drop doesn't drop it again
+                unsafe { core::ptr::drop_in_place(p) }
+            } else {
+                g.keep();
+            }
+        }
+    }
+
+    // removes the elements in the range and gives them out as an owning double-ended iterator.
+    // the elements that are not taken are dropped when the iterator is dropped. panics if the range is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> StackSimpleDrain<'_, T, C> {
+        let (start, end) = crate::drain::__resolve_range(range, self.len);
+        let tail_len = self.len - end;
+        self.len = start; // if the drain is leaked the range and the tail are leaked with it, nothing gets dropped twice
+        StackSimpleDrain{
+            s: self,
+            front: start,
+            back: end,
+            tail_start: end,
+            tail_len,
+        }
+    }
+
+    // moves the elements from index at to the end into a new list. self keeps the elements [0, at).
+    // panics if at > len.
+    pub fn split_off<const D: usize>(&mut self, at: usize) -> Result<StackSimple<T, D>, CapacityError<()>> { // err if the new list doesn't have room for the elements
+        if at > self.len {
+            panic!("split_off index (is {}) should be <= len (is {})", at, self.len);
+        }
+        let count = self.len - at;
+        if D < count {
+            return Err(CapacityError::new(()));
+        }
+        let mut other = StackSimple::<T, D>::new();
+        unsafe { core::ptr::copy_nonoverlapping(self.data.as_ptr().add(at), other.data.as_mut_ptr(), count) }
+        other.len = count;
+        self.len = at;
+        Ok(other)
+    }
+
+    // clones all of the slice onto the end. err if they don't all fit, in that case nothing is added.
+    pub fn extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError<()>>
+    where T: Clone {
+        if C - self.len < other.len() {
+            return Err(CapacityError::new(()));
+        }
+        for element in other {
+            self.push(element.clone()).map_err(|_| ()).unwrap(); // unwrap bc we checked there is room
+        }
+        Ok(())
+    }
+
+    // truncates, or fills up to new_len with clones of value. err if new_len > C, value comes back in the error and nothing changes.
+    pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), CapacityError<T>>
+    where T: Clone {
+        if new_len > C {
+            return Err(CapacityError::new(value));
+        }
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return Ok(());
+        }
+        while self.len + 1 < new_len {
+            self.push(value.clone()).map_err(|_| ()).unwrap(); // unwrap bc new_len <= C
+        }
+        self.push(value).map_err(|_| ()).unwrap(); // the last one gets the value itself
+        Ok(())
+    }
 }
 
 
@@ -94,6 +274,99 @@ impl<T, const C: usize> FromIterator<T> for StackSimple<T, C> {
     }    
 }
 
+// retain and dedup go through the elements once and move the kept ones down over the removed ones.
+// if the callback panics, the drop of the guard moves the rest down too, so the list stays valid and nothing is dropped twice.
+struct CompactGuard<'a, T, const C: usize> {
+    s: &'a mut StackSimple<T, C>,
+    processed: usize,    // the elements before this are either kept or dropped
+    kept: usize,         // the kept elements are in [0, kept)
+    original_len: usize,
+}
+impl<'a, T, const C: usize> CompactGuard<'a, T, C> {
+    fn new(s: &'a mut StackSimple<T, C>) -> Self {
+        let original_len = s.len;
+        s.len = 0; // the guard owns the elements until it is dropped
+        Self { s, processed: 0, kept: 0, original_len }
+    }
+    // keeps the element at processed
+    fn keep(&mut self) {
+        if self.kept != self.processed {
+            unsafe { core::ptr::copy_nonoverlapping(self.s.data[self.processed].as_ptr(), self.s.data[self.kept].as_mut_ptr(), 1) }
+        }
+        self.kept += 1;
+        self.processed += 1;
+    }
+}
+impl<'a, T, const C: usize> Drop for CompactGuard<'a, T, C> {
+    fn drop(&mut self) {
+        let rest = self.original_len - self.processed; // not 0 only if the callback panicked
+        unsafe {
+            let p = self.s.data.as_mut_ptr();
+            core::ptr::copy(p.add(self.processed), p.add(self.kept), rest);
+        }
+        self.s.len = self.kept + rest;
+    }
+}
+
+// drain
+
+pub struct StackSimpleDrain<'a, T, const C: usize> {
+    s: &'a mut StackSimple<T, C>,
+    front: usize,       // the elements in [front, back) are not given out yet
+    back: usize,
+    tail_start: usize,  // the elements after the range, they get moved down to len when the drain is dropped
+    tail_len: usize,
+}
+impl<'a, T, const C: usize> Iterator for StackSimpleDrain<'a, T, C> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(unsafe { self.s.data[self.front - 1].assume_init_read() }) // unsafe ok bc it is in the range and given out only once
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+impl<'a, T, const C: usize> DoubleEndedIterator for StackSimpleDrain<'a, T, C> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(unsafe { self.s.data[self.back].assume_init_read() }) // unsafe ok bc it is in the range and given out only once
+    }
+}
+impl<'a, T, const C: usize> ExactSizeIterator for StackSimpleDrain<'a, T, C> {}
+impl<'a, T, const C: usize> FusedIterator     for StackSimpleDrain<'a, T, C> {}
+impl<'a, T, const C: usize> Drop for StackSimpleDrain<'a, T, C> {
+    fn drop(&mut self) {
+        // moves the tail down even if dropping one of the remaining elements panics
+        struct MoveTail<'b, 'a, T, const C: usize>(&'b mut StackSimpleDrain<'a, T, C>);
+        impl<'b, 'a, T, const C: usize> Drop for MoveTail<'b, 'a, T, C> {
+            fn drop(&mut self) {
+                let d = &mut *self.0;
+                unsafe {
+                    let p = d.s.data.as_mut_ptr();
+                    core::ptr::copy(p.add(d.tail_start), p.add(d.s.len), d.tail_len);
+                }
+                d.s.len += d.tail_len;
+            }
+        }
+        let guard = MoveTail(self);
+        guard.0.for_each(drop);
+    }
+}
+
+// drops exactly the first len items, the rest of the data is uninitialized
+impl<T, const C: usize> Drop for StackSimple<T, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
 // into-iterator
 
 pub struct StackSimpleIterator<T, const C: usize> {
@@ -102,6 +375,7 @@ pub struct StackSimpleIterator<T, const C: usize> {
 }
 impl<T, const C: usize> Iterator for StackSimpleIterator<T, C> {
     type Item = T;
+    #[allow(clippy::identity_op)] // the 0 + is the start of the data, it reads better next to the offset
     fn next(&mut self) -> Option<T> {
         if self.s.len - self.number_of_items_uninitialized_at_the_begining == 0 {
             return None;
@@ -124,6 +398,15 @@ impl<T, const C: usize> DoubleEndedIterator for StackSimpleIterator<T, C> {
         Some(self.s.pop().unwrap()) // unwrap cause we checked there is at least one item
     }
 }
+// the items in [number_of_items_uninitialized_at_the_begining, len) were not given out yet
+impl<T, const C: usize> Drop for StackSimpleIterator<T, C> {
+    fn drop(&mut self) {
+        let start = self.number_of_items_uninitialized_at_the_begining;
+        let end = self.s.len;
+        self.s.len = 0; // the StackSimple inside doesn't drop anything then, and a panic in one of the drops can't get them dropped again
+        unsafe { core::ptr::drop_in_place(slice_assume_init_mut(&mut self.s.data[start..end])) }
+    }
+}
 impl<T, const C: usize> ExactSizeIterator for StackSimpleIterator<T, C> {}
 impl<T, const C: usize> FusedIterator     for StackSimpleIterator<T, C> {}
 
@@ -168,6 +451,7 @@ impl<'a, T, const C: usize> Iterator for StackSimpleRChunks<'a, T, C> {
         self.number_of_items_served += end - start;
         Some(&self.s[start..end])
     }
+    #[allow(clippy::manual_is_multiple_of)] // is_multiple_of is too new for older toolchains
     fn size_hint(&self) -> (usize, Option<usize>) {
         let number_of_items_left = self.s.len - self.number_of_items_served;
         let mut number_of_chunks_left = number_of_items_left / self.chunk_size;
@@ -196,124 +480,183 @@ impl<T, const C: usize> Extend<T> for StackSimple<T, C> {
 
 
 
-// index traits
+// index traits. they index the slice of the valid items, so they take the same indexes as a slice (usize and all the ranges)
+// and panic in the same cases. e.g. s[s.len()..] is an empty slice.
 
-impl<T, const C: usize> core::ops::Index<usize> for StackSimple<T, C> {
-    type Output = T;
-    fn index(&self, index: usize) -> &Self::Output {
-        if index >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { &*self.data[index].as_ptr() } // unsafe ok bc we checked the index is within range
+impl<T, I: SliceIndex<[T]>, const C: usize> core::ops::Index<I> for StackSimple<T, C> {
+    type Output = I::Output;
+    fn index(&self, index: I) -> &Self::Output {
+        &self.as_slice()[index]
     }
 }
-impl<T, const C: usize> core::ops::Index<Range<usize>> for StackSimple<T, C> {
-    type Output = <Range<usize> as SliceIndex<[T]>>::Output;
-    fn index(&self, index: Range<usize>) -> &Self::Output {
-        if index.start >= self.len || index.end > self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_ref(&self.data[index]) }
+impl<T, I: SliceIndex<[T]>, const C: usize> core::ops::IndexMut<I> for StackSimple<T, C> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.as_mut_slice()[index]
     }
 }
-impl<T, const C: usize> core::ops::Index<RangeFrom<usize>> for StackSimple<T, C> {
-    type Output = <Range<usize> as SliceIndex<[T]>>::Output;
-    fn index(&self, index: RangeFrom<usize>) -> &Self::Output {
-        if index.start >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_ref(&self.data[index.start..self.len]) }
+
+
+// deref to the slice of the valid items, so all the slice methods (sort, binary_search, windows, chunks, ...) work directly
+
+impl<T, const C: usize> Deref for StackSimple<T, C> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
     }
 }
-impl<T, const C: usize> core::ops::Index<RangeTo<usize>> for StackSimple<T, C> {
-    type Output = <Range<usize> as SliceIndex<[T]>>::Output;
-    fn index(&self, index: RangeTo<usize>) -> &Self::Output {
-        if index.end > self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_ref(&self.data[index]) }
+impl<T, const C: usize> DerefMut for StackSimple<T, C> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
     }
 }
-impl<T, const C: usize> core::ops::Index<RangeFull> for StackSimple<T, C> {
-    type Output = <Range<usize> as SliceIndex<[T]>>::Output;
-    fn index(&self, _index: RangeFull) -> &Self::Output {
-        unsafe { slice_assume_init_ref(&self.data[..self.len]) }
+impl<T, const C: usize> AsRef<[T]> for StackSimple<T, C> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
     }
 }
-impl<T, const C: usize> core::ops::Index<RangeInclusive<usize>> for StackSimple<T, C> {
-    type Output = <Range<usize> as SliceIndex<[T]>>::Output;
-    fn index(&self, index: RangeInclusive<usize>) -> &Self::Output {
-        if *index.start() >= self.len || *index.end() >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_ref(&self.data[index]) }
+impl<T, const C: usize> AsMut<[T]> for StackSimple<T, C> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
     }
 }
-impl<T, const C: usize> core::ops::Index<RangeToInclusive<usize>> for StackSimple<T, C> {
-    type Output = <Range<usize> as SliceIndex<[T]>>::Output;
-    fn index(&self, index: RangeToInclusive<usize>) -> &Self::Output {
-        if index.end >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_ref(&self.data[index]) }
+// the Eq, Ord and Hash impls below are the ones of the slice, so these are allowed
+impl<T, const C: usize> core::borrow::Borrow<[T]> for StackSimple<T, C> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T, const C: usize> core::borrow::BorrowMut<[T]> for StackSimple<T, C> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
     }
 }
 
 
-// indexmut
 
+// conversions. the elements are moved with one ptr copy, never cloned, except from a slice.
 
-impl<T, const C: usize> core::ops::IndexMut<usize> for StackSimple<T, C> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        if index >= self.len {
-            panic!("index out of bounds");
+// compile time check for the conversions from an array of len M
+struct ArrayFits<const M: usize, const C: usize>;
+impl<const M: usize, const C: usize> ArrayFits<M, C> {
+    const OK: () = assert!(M <= C, "the array is longer than the capacity C");
+}
+
+impl<T, const C: usize> StackSimple<T, C> {
+    // the array if the list is full, otherwise the list comes back in the error
+    pub fn into_inner(self) -> Result<[T; C], Self> {
+        if self.len != C {
+            return Err(self);
         }
-        unsafe { &mut *self.data[index].as_mut_ptr() } // unsafe ok bc we checked the index is within range
+        let s = core::mem::ManuallyDrop::new(self); // the elements move into the array, so no drop
+        Ok(unsafe { core::ptr::read(s.data.as_ptr() as *const [T; C]) }) // unsafe ok bc all C items are initialized
     }
-}
-impl<T, const C: usize> core::ops::IndexMut<Range<usize>> for StackSimple<T, C> {
-    fn index_mut(&mut self, index: Range<usize>) -> &mut Self::Output {
-        if index.start >= self.len || index.end > self.len {
-            panic!("index out of bounds");
+
+    // moves the elements into a StackSimple with capacity D. err if len > D, the list comes back in the error.
+    pub fn resize_capacity<const D: usize>(self) -> Result<StackSimple<T, D>, CapacityError<Self>> {
+        if self.len > D {
+            return Err(CapacityError::new(self));
         }
-        unsafe { slice_assume_init_mut(&mut self.data[index]) }
+        let mut s = core::mem::ManuallyDrop::new(self); // the elements move into the other one, so no drop
+        let mut other = StackSimple::<T, D>::new();
+        unsafe { core::ptr::copy_nonoverlapping(s.data.as_ptr(), other.data.as_mut_ptr(), s.len) }
+        other.len = s.len;
+        s.len = 0;
+        Ok(other)
     }
 }
-impl<T, const C: usize> core::ops::IndexMut<RangeFrom<usize>> for StackSimple<T, C> {
-    fn index_mut(&mut self, index: RangeFrom<usize>) -> &mut Self::Output {
-        if index.start >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_mut(&mut self.data[index.start..self.len]) }
+
+// any array that fits. StackSimple::<u8, 8>::from([1, 2, 3]) is ok, StackSimple::<u8, 2>::from([1, 2, 3]) doesn't compile
+impl<T, const M: usize, const C: usize> From<[T; M]> for StackSimple<T, C> {
+    fn from(a: [T; M]) -> Self {
+        let () = ArrayFits::<M, C>::OK;
+        let a = core::mem::ManuallyDrop::new(a); // the elements move into the list, so no drop
+        let mut s = Self::new();
+        unsafe { core::ptr::copy_nonoverlapping(a.as_ptr(), s.data.as_mut_ptr() as *mut T, M) }
+        s.len = M;
+        s
     }
 }
-impl<T, const C: usize> core::ops::IndexMut<RangeTo<usize>> for StackSimple<T, C> {
-    fn index_mut(&mut self, index: RangeTo<usize>) -> &mut Self::Output {
-        if index.end > self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_mut(&mut self.data[index]) }
+
+// clones the elements. err if the slice is longer than C
+impl<T: Clone, const C: usize> TryFrom<&[T]> for StackSimple<T, C> {
+    type Error = CapacityError<()>;
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        let mut s = Self::new();
+        s.extend_from_slice(slice)?;
+        Ok(s)
     }
 }
-impl<T, const C: usize> core::ops::IndexMut<RangeFull> for StackSimple<T, C> {
-    fn index_mut(&mut self, _index: RangeFull) -> &mut Self::Output {
-        unsafe { slice_assume_init_mut(&mut self.data[..self.len]) }
+
+// only a full list converts, otherwise the list comes back in the error
+impl<T, const C: usize> TryFrom<StackSimple<T, C>> for [T; C] {
+    type Error = StackSimple<T, C>;
+    fn try_from(s: StackSimple<T, C>) -> Result<Self, Self::Error> {
+        s.into_inner()
     }
 }
-impl<T, const C: usize> core::ops::IndexMut<RangeInclusive<usize>> for StackSimple<T, C> {
-    fn index_mut(&mut self, index: RangeInclusive<usize>) -> &mut Self::Output {
-        if *index.start() >= self.len || *index.end() >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_mut(&mut self.data[index]) }
+
+
+
+// std traits. these all go by the valid items, the same as the slice &self[..]. the comparisons work across capacities.
+
+impl<T: Clone, const C: usize> Clone for StackSimple<T, C> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
     }
 }
-impl<T, const C: usize> core::ops::IndexMut<RangeToInclusive<usize>> for StackSimple<T, C> {
-    fn index_mut(&mut self, index: RangeToInclusive<usize>) -> &mut Self::Output {
-        if index.end >= self.len {
-            panic!("index out of bounds");
-        }
-        unsafe { slice_assume_init_mut(&mut self.data[index]) }
+impl<T, const C: usize> Default for StackSimple<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: PartialEq<U>, U, const C: usize, const D: usize> PartialEq<StackSimple<U, D>> for StackSimple<T, C> {
+    fn eq(&self, other: &StackSimple<U, D>) -> bool {
+        self[..] == other[..]
+    }
+}
+impl<T: PartialEq<U>, U, const C: usize> PartialEq<[U]> for StackSimple<T, C> {
+    fn eq(&self, other: &[U]) -> bool {
+        self[..] == *other
+    }
+}
+impl<'a, T: PartialEq<U>, U, const C: usize> PartialEq<&'a [U]> for StackSimple<T, C> {
+    fn eq(&self, other: &&'a [U]) -> bool {
+        self[..] == **other
+    }
+}
+impl<T: PartialEq<U>, U, const C: usize, const D: usize> PartialEq<[U; D]> for StackSimple<T, C> {
+    fn eq(&self, other: &[U; D]) -> bool {
+        self[..] == other[..]
+    }
+}
+impl<T: Eq, const C: usize> Eq for StackSimple<T, C> {}
+impl<T: PartialOrd, const C: usize, const D: usize> PartialOrd<StackSimple<T, D>> for StackSimple<T, C> {
+    fn partial_cmp(&self, other: &StackSimple<T, D>) -> Option<core::cmp::Ordering> {
+        self[..].partial_cmp(&other[..])
+    }
+}
+impl<T: Ord, const C: usize> Ord for StackSimple<T, C> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self[..].cmp(&other[..])
+    }
+}
+impl<T: core::hash::Hash, const C: usize> core::hash::Hash for StackSimple<T, C> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self[..].hash(state)
+    }
+}
+impl<'a, T, const C: usize> IntoIterator for &'a StackSimple<T, C> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const C: usize> IntoIterator for &'a mut StackSimple<T, C> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
@@ -324,6 +667,8 @@ impl<T, const C: usize> core::ops::IndexMut<RangeToInclusive<usize>> for StackSi
 
 
 // taken from nightly https://doc.rust-lang.org/1.80.1/src/core/mem/maybe_uninit.rs.html#967
+/// # Safety
+/// all the items of slice must be initialized
 pub const unsafe fn slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
     // SAFETY: casting `slice` to a `*const [T]` is safe since the caller guarantees that
     // `slice` is initialized, and `MaybeUninit` is guaranteed to have the same layout as `T`.
@@ -331,6 +676,8 @@ pub const unsafe fn slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
     // reference and thus guaranteed to be valid for reads.
     unsafe { &*(slice as *const [MaybeUninit<T>] as *const [T]) }
 }
+/// # Safety
+/// all the items of slice must be initialized
 pub unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
     // SAFETY: similar to safety notes for `slice_get_ref`, but we have a
     // mutable reference which is also guaranteed to be valid for writes.
@@ -349,9 +696,9 @@ fn slice_as_maybeuninit<'a, T>(s: &'a [T]) -> &'a [MaybeUninit<T>] {
     unsafe { &*(s as *const [T] as *const [MaybeUninit<T>]) }
 }
 */
-fn array_as_maybeuninit<T, const C: usize>(a: [T; C]) -> [MaybeUninit<T>; C] {
+const fn array_as_maybeuninit<T, const C: usize>(a: [T; C]) -> [MaybeUninit<T>; C] {
     // can't use transmute bc const generic size issue// https://github.com/rust-lang/rust/issues/62875
-    let d = unsafe { core::ptr::read((&a as *const [T; C]).cast::<[MaybeUninit<T>; C]>()) };
-    core::mem::forget(a);
-    d
+    // ManuallyDrop instead of forget bc a const fn can't have a drop of a in any code path
+    let a = core::mem::ManuallyDrop::new(a);
+    unsafe { core::ptr::read((&a as *const core::mem::ManuallyDrop<[T; C]>).cast::<[MaybeUninit<T>; C]>()) }
 }
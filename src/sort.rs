@@ -0,0 +1,130 @@
+use super::*;
+use core::cmp::Ordering;
+
+// sorting only rewrites the prev/next links in the main_memory, the elements never move.
+// so NodeHandles stay valid through a sort.
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> StackStructure<T, N, Idx, L> {
+
+    pub fn sort(&mut self)
+    where T: Ord {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key_of_the_element: F) {
+        self.sort_by(|a, b| key_of_the_element(a).cmp(&key_of_the_element(b)));
+    }
+
+    // stable bottom-up merge sort. O(n log n) compares, no extra memory and no recursion.
+    // each pass merges the neighbouring runs of length run_len into runs of length run_len*2.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let (mut head, _tail) = match self.head_and_tail {
+            None => return, // empty
+            Some(head_and_tail) => head_and_tail,
+        };
+        let mut run_len: usize = 1;
+        loop {
+            let mut p: Option<usize> = Some(head); // start of the left run
+            let mut new_head: Option<usize> = None;
+            let mut new_tail: Option<usize> = None;
+            let mut number_of_merges: usize = 0;
+
+            while let Some(left_run_start) = p {
+                number_of_merges += 1;
+
+                // find the start of the right run
+                let mut q: Option<usize> = Some(left_run_start);
+                let mut p_len: usize = 0;
+                for _ in 0..run_len {
+                    p_len += 1;
+                    q = self.main_memory[q.unwrap()].next(); // unwrap bc we break when it is None
+                    if q.is_none() {
+                        break;
+                    }
+                }
+                let mut q_len: usize = run_len;
+
+                // merge the two runs onto the end of the new list
+                loop {
+                    let take_from_p: bool = if p_len == 0 {
+                        if q_len == 0 || q.is_none() {
+                            break;
+                        }
+                        false
+                    } else if q_len == 0 || q.is_none() {
+                        true
+                    } else {
+                        // on equal take from the left run, that is what keeps it stable
+                        compare(
+                            self.__element(p.unwrap()).unwrap(),
+                            self.__element(q.unwrap()).unwrap(),
+                        ) != Ordering::Greater
+                    };
+                    let node_i: usize = if take_from_p {
+                        let node_i = p.unwrap();
+                        p = self.main_memory[node_i].next();
+                        p_len -= 1;
+                        node_i
+                    } else {
+                        let node_i = q.unwrap();
+                        q = self.main_memory[node_i].next();
+                        q_len -= 1;
+                        node_i
+                    };
+                    match new_tail {
+                        None => new_head = Some(node_i),
+                        Some(new_tail_i) => self.main_memory[new_tail_i].set_next(Some(node_i)),
+                    }
+                    self.main_memory[node_i].set_prev(new_tail);
+                    new_tail = Some(node_i);
+                }
+
+                p = q;
+            }
+
+            let new_tail = new_tail.unwrap(); // the list is not empty
+            self.main_memory[new_tail].set_next(None);
+            head = new_head.unwrap();
+
+            if number_of_merges <= 1 {
+                self.head_and_tail = Some((head, new_tail));
+                return;
+            }
+            run_len *= 2;
+        }
+    }
+
+    // both lists must be sorted already. the result is sorted and stable, on equal elements the ones from self come first.
+    // O(n+m). the elements of other are moved into the nodes of self.
+    // err if self doesn't have room for all of other, in that case the other list comes back in the error and self is as it was.
+    pub fn merge<const M: usize, Idx2: LinkIndex, L2: Layout>(&mut self, other: StackStructure<T, M, Idx2, L2>) -> Result<(), CapacityError<StackStructure<T, M, Idx2, L2>>>
+    where T: Ord {
+        self.merge_by(other, |a, b| a.cmp(b))
+    }
+
+    pub fn merge_by<const M: usize, Idx2: LinkIndex, L2: Layout, F: FnMut(&T, &T) -> Ordering>(&mut self, mut other: StackStructure<T, M, Idx2, L2>, mut compare: F) -> Result<(), CapacityError<StackStructure<T, M, Idx2, L2>>> {
+        if N - self.len < other.len {
+            return Err(CapacityError::new(other));
+        }
+        let mut current_node_i: Option<usize> = self.head_and_tail.map(|(head, _tail)| head); // the first node of self that is not already before the merged elements
+        while let Some(element) = other.pop_front() {
+            while let Some(node_i) = current_node_i {
+                if compare(self.__element(node_i).unwrap(), &element) == Ordering::Greater {
+                    break;
+                }
+                current_node_i = self.main_memory[node_i].next();
+            }
+            let new_node_i = self.__get_new_node_from_free_list().unwrap(); // unwrap bc we checked there is room
+            self.__put_element(new_node_i, element);
+            match current_node_i {
+                None => self.__insert_node_as_tail(new_node_i),
+                Some(node_i) => match self.main_memory[node_i].prev() {
+                    None => self.__insert_node_as_head(new_node_i),
+                    Some(prev_i) => self.__insert_node_after_node(new_node_i, prev_i),
+                }
+            }
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use crate::simple::StackSimple;
+use crate::error::CapacityError;
+
+// FOR THE TEXT // fixed capacity utf-8 string on the stack
+// C is the capacity in bytes, not in chars. the bytes are always valid utf-8, every method keeps it that way.
+// write! works directly into it through fmt::Write.
+
+pub struct StackString<const C: usize> {
+    bytes: StackSimple<u8, C>, // keep private, the utf-8 depends on it
+}
+
+impl<const C: usize> StackString<C> {
+    pub const fn new() -> Self {
+        Self {
+            bytes: StackSimple::new(),
+        }
+    }
+
+    pub const fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.bytes.as_slice()) } // unsafe ok bc the bytes are always valid utf-8
+    }
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { core::str::from_utf8_unchecked_mut(self.bytes.as_mut_slice()) } // unsafe ok bc the bytes are always valid utf-8
+    }
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    // in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.bytes.is_full()
+    }
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+    pub fn remaining_capacity(&self) -> usize {
+        C - self.bytes.len()
+    }
+
+    pub fn push(&mut self, ch: char) -> Result<(), CapacityError<char>> { // err if the utf-8 of ch doesn't fit. ch comes back in the error
+        let mut buf = [0u8; 4];
+        self.push_str(ch.encode_utf8(&mut buf))
+            .map_err(|_| CapacityError::new(ch))
+    }
+
+    // all of s or nothing
+    pub fn push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> { // err if s doesn't fit. s comes back in the error
+        if s.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(s));
+        }
+        self.bytes.extend_from_slice(s.as_bytes()).unwrap(); // unwrap bc we checked there is room
+        Ok(())
+    }
+
+    // pushes as much of s as fits, cut on a char boundary. err with the part that didn't fit.
+    pub fn push_str_truncating<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        let mut end = s.len().min(self.remaining_capacity());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (fits, rest) = s.split_at(end);
+        self.push_str(fits).unwrap(); // unwrap bc fits is not longer than the remaining capacity
+        if !rest.is_empty() {
+            return Err(CapacityError::new(rest));
+        }
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<char> { // none if empty
+        let ch = self.as_str().chars().next_back()?;
+        self.bytes.truncate(self.len() - ch.len_utf8());
+        Some(ch)
+    }
+
+    // drops the bytes after new_len. does nothing if the string is not longer than new_len.
+    // panics if new_len is not on a char boundary, same as String::truncate.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        if !self.as_str().is_char_boundary(new_len) {
+            panic!("new_len (is {}) is not on a char boundary", new_len);
+        }
+        self.bytes.truncate(new_len);
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+}
+
+
+// on overflow this writes as much as fits and then returns fmt::Error, so a log line that is too long gets cut off, not lost.
+// write! stops at the error, so nothing is written after the cut.
+impl<const C: usize> fmt::Write for StackString<C> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str_truncating(s).map_err(|_| fmt::Error)
+    }
+    fn write_char(&mut self, ch: char) -> fmt::Result {
+        self.push(ch).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const C: usize> Deref for StackString<C> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl<const C: usize> DerefMut for StackString<C> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+impl<const C: usize> AsRef<str> for StackString<C> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl<const C: usize> AsRef<[u8]> for StackString<C> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+// the Eq, Ord and Hash impls below are the ones of str, so this is allowed
+impl<const C: usize> core::borrow::Borrow<str> for StackString<C> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// err if s is longer than C bytes. s comes back in the error
+impl<'a, const C: usize> TryFrom<&'a str> for StackString<C> {
+    type Error = CapacityError<&'a str>;
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let mut string = Self::new();
+        string.push_str(s)?;
+        Ok(string)
+    }
+}
+
+
+// std traits. they all go by the str. the comparisons work across capacities.
+
+impl<const C: usize> fmt::Display for StackString<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+impl<const C: usize> fmt::Debug for StackString<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+impl<const C: usize> Clone for StackString<C> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+impl<const C: usize> Default for StackString<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const C: usize, const D: usize> PartialEq<StackString<D>> for StackString<C> {
+    fn eq(&self, other: &StackString<D>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl<const C: usize> PartialEq<str> for StackString<C> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl<'a, const C: usize> PartialEq<&'a str> for StackString<C> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl<const C: usize> PartialEq<StackString<C>> for str {
+    fn eq(&self, other: &StackString<C>) -> bool {
+        self == other.as_str()
+    }
+}
+impl<const C: usize> PartialEq<StackString<C>> for &str {
+    fn eq(&self, other: &StackString<C>) -> bool {
+        *self == other.as_str()
+    }
+}
+impl<const C: usize> Eq for StackString<C> {}
+impl<const C: usize, const D: usize> PartialOrd<StackString<D>> for StackString<C> {
+    fn partial_cmp(&self, other: &StackString<D>) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+impl<const C: usize> Ord for StackString<C> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+impl<const C: usize> core::hash::Hash for StackString<C> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
@@ -1,6 +1,8 @@
 use super::*;
 
 
+// the baseline test ignores the results of insert and keeps ms2 around unused
+#[allow(unused_must_use, unused_variables)]
 #[test]
 fn test_1() {
     let mut ms = StackStructure::<u64, 5>::new();
@@ -193,3 +195,1277 @@ fn test_map_rchunks() {
     }
     
 }
+
+#[test]
+fn test_cursor() {
+    let mut ms = StackStructure::<_, 8>::from_iter([1, 2, 3, 4]);
+    
+    let mut c = ms.cursor_front_mut();
+    c.move_next();
+    assert_eq!(Some(1), c.index());
+    assert_eq!(Some(&mut 2), c.current());
+    c.insert_before(10).unwrap();
+    c.insert_after(20).unwrap();
+    assert_eq!(Some(2), c.index());
+    assert_eq!(Some(&mut 10), c.peek_prev());
+    assert_eq!(Some(2), c.remove_current());
+    assert_eq!(Some(&mut 20), c.current());
+    assert_eq!(Ok(20), c.replace_current(21));
+    c.move_next();
+    c.move_next();
+    c.move_next();
+    assert_eq!(None, c.index()); // ghost
+    assert_eq!(None, c.remove_current());
+    assert_eq!(Err(IndexOutOfBounds::new(0, 5, 5)), c.replace_current(0));
+    c.insert_before(5).unwrap();  // tail
+    c.insert_after(0).unwrap();   // head
+    
+    assert_eq!([0, 1, 10, 21, 3, 4, 5], ms.iter().copied().collect::<StackSimple<_, 7>>()[..]);
+    
+    let mut c = ms.cursor_back();
+    assert_eq!(Some(6), c.index());
+    assert_eq!(Some(&5), c.current());
+    assert_eq!(Some(&4), c.peek_prev());
+    assert_eq!(None, c.peek_next());
+    c.move_next();
+    assert_eq!(Some(&0), c.peek_next());
+    c.move_prev();
+    c.move_prev();
+    assert_eq!(Some(5), c.index());
+    assert_eq!(Some(&4), c.current());
+    
+    let mut c = ms.cursor_front_mut();
+    c.insert_after(100).unwrap();
+    assert_eq!(Err(CapacityError::new(101)), c.insert_after(101)); // full
+    while c.remove_current().is_some() {}
+    assert_eq!(0, ms.len());
+    
+    let mut c = ms.cursor_back_mut();
+    assert_eq!(None, c.index());
+    c.insert_after(7).unwrap();
+    c.move_next();
+    assert_eq!(Some(&mut 7), c.current());
+    assert_eq!(1, ms.len());
+}
+
+#[test]
+fn test_handles() {
+    let mut ms = StackStructure::<_, 4>::new();
+    let a = ms.push_with_handle("a").unwrap();
+    let c = ms.push_with_handle("c").unwrap();
+    let b = ms.insert_after_handle(a, "b").unwrap();
+    let z = ms.insert_with_handle(0, "z").unwrap();
+    
+    assert_eq!(Some(&"b"), ms.get_by_handle(b));
+    *ms.get_mut_by_handle(c).unwrap() = "cc";
+    assert_eq!(Some("z"), ms.remove_by_handle(z));
+    assert_eq!(None, ms.get_by_handle(z)); // stale
+    assert_eq!(None, ms.remove_by_handle(z));
+    
+    // the freed slot gets reused but the old handle stays stale
+    let d = ms.push_with_handle("d").unwrap();
+    assert_eq!(None, ms.get_by_handle(z));
+    assert_eq!(Some(&"d"), ms.get_by_handle(d));
+    assert_eq!(Err(InsertError::StaleHandle(StaleHandle::new("x"))), ms.insert_after_handle(z, "x"));
+    
+    assert_eq!(Some(&"a"), ms.get_by_handle(a));
+    assert_eq!(Some(&"cc"), ms.get_by_handle(c));
+    assert_eq!(["a", "b", "cc", "d"], ms.iter().copied().collect::<StackSimple<_, 4>>()[..]);
+}
+
+#[test]
+fn test_errors_give_back_the_value() {
+    use std::string::{String, ToString};
+    
+    let mut ms = StackStructure::<String, 2>::new();
+    
+    // out of bounds on an empty list must not take a node from the free list
+    let e = ms.insert(1, "a".to_string()).unwrap_err();
+    assert!(matches!(e, InsertError::OutOfBounds(_)));
+    assert_eq!("a", e.into_inner());
+    assert_eq!((None, 0), (ms.free_list, ms.fresh_nodes_start));
+    assert_eq!(0, ms.len());
+    
+    ms.push("b".to_string()).unwrap();
+    ms.push("c".to_string()).unwrap();
+    
+    let e = ms.push("d".to_string()).unwrap_err();
+    assert_eq!("d", e.element());
+    assert_eq!("insufficient capacity", e.to_string());
+    let e = ms.insert(5, "e".to_string()).unwrap_err();
+    assert_eq!("index out of bounds: the len is 2 but the index is 5", e.to_string());
+    assert_eq!("e", e.into_inner());
+    assert_eq!(IndexOutOfBounds::new((), 2, 2), ms.delete(2).unwrap_err());
+    let e = ms.set(3, "f".to_string()).unwrap_err();
+    assert_eq!((3, 2, "f".to_string()), (e.index(), e.len(), e.into_inner()));
+    assert!(ms.is_full());
+    assert_eq!(2, ms.len());
+    
+    // the whole capacity is still usable
+    ms.delete(0).unwrap();
+    ms.delete(0).unwrap();
+    ms.push("g".to_string()).unwrap();
+    ms.push("h".to_string()).unwrap();
+    
+    let mut s = StackSimple::<String, 1>::new();
+    s.push("i".to_string()).unwrap();
+    assert_eq!("j", s.push("j".to_string()).unwrap_err().into_inner());
+    
+    let mut map = StackMap::<u8, String, 1>::new();
+    map.insert(1, "k".to_string()).unwrap();
+    assert_eq!((0, "l".to_string()), map.insert(0, "l".to_string()).unwrap_err().into_inner());
+    assert_eq!((2, "m".to_string()), map.insert(2, "m".to_string()).unwrap_err().into_inner());
+    assert_eq!(Some("k".to_string()), map.insert(1, "n".to_string()).unwrap());
+}
+
+#[test]
+fn test_positional_access_from_both_ends() {
+    use std::vec::Vec;
+    
+    let mut ms = StackStructure::<usize, 32>::new();
+    let mut v = Vec::new();
+    for i in 0..32 {
+        let at = (i * 7) % (v.len() + 1);
+        ms.insert(at, i).unwrap();
+        v.insert(at, i);
+    }
+    for (i, x) in v.iter().enumerate() {
+        assert_eq!(Some(x), ms.get(i));
+    }
+    assert_eq!(None, ms.get(32));
+    for i in (0..32).step_by(3) {
+        *ms.get_mut(i).unwrap() += 100;
+        v[i] += 100;
+        assert_eq!(Ok(v[31 - i]), ms.set(31 - i, 1000 + i));
+        v[31 - i] = 1000 + i;
+    }
+    while !v.is_empty() {
+        let at = (v.len() / 3 + 1) % v.len();
+        assert_eq!(Ok(v.remove(at)), ms.delete(at));
+        assert_eq!(v[..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    }
+    assert_eq!(0, ms.len());
+}
+
+#[test]
+fn test_deque() {
+    let mut ms = StackStructure::<_, 4>::new();
+    assert!(ms.is_empty());
+    assert_eq!(4, ms.capacity());
+    assert_eq!(None, ms.pop_front());
+    assert_eq!(None, ms.back());
+    
+    ms.push_back(2).unwrap();
+    ms.push_front(1).unwrap();
+    ms.push_back(3).unwrap();
+    ms.push_front(0).unwrap();
+    assert!(ms.is_full());
+    assert_eq!(Err(CapacityError::new(9)), ms.push_front(9));
+    
+    assert_eq!(Some(&0), ms.front());
+    assert_eq!(Some(&3), ms.back());
+    *ms.front_mut().unwrap() += 10;
+    *ms.back_mut().unwrap() += 10;
+    assert_eq!(Some(10), ms.pop_front());
+    assert_eq!(Some(13), ms.pop_back());
+    assert_eq!(Some(1), ms.pop_front());
+    assert_eq!(Some(&2), ms.front());
+    assert_eq!(Some(&2), ms.back());
+    assert_eq!(Some(2), ms.pop_back());
+    assert_eq!(None, ms.pop_back());
+    
+    ms.extend([5, 6, 7, 8]);
+    ms.truncate(6);
+    assert_eq!(4, ms.len());
+    ms.truncate(2);
+    assert_eq!(Some(&6), ms.back());
+    ms.clear();
+    assert!(ms.is_empty());
+    ms.extend([1, 2, 3, 4]);
+    assert!(ms.is_full());
+}
+
+#[test]
+fn test_zero_capacity() {
+    let mut ms = StackStructure::<u8, 0>::new();
+    assert!(ms.is_full());
+    assert!(ms.is_empty());
+    assert_eq!(Err(CapacityError::new(1)), ms.push_front(1));
+    assert_eq!(Err(CapacityError::new(1)), ms.push_back(1));
+}
+
+#[test]
+fn test_sort() {
+    use std::vec::Vec;
+    
+    let mut ms = StackStructure::<(u8, usize), 50>::new();
+    let mut handles = Vec::new();
+    for i in 0..50 {
+        handles.push(ms.push_with_handle((((i * 37) % 11) as u8, i)).unwrap());
+    }
+    let mut v: Vec<_> = ms.iter().copied().collect();
+    
+    ms.sort_by_key(|t| t.0); // stable so the second field stays in order within equal keys
+    v.sort_by_key(|t| t.0);
+    assert_eq!(v, ms.iter().copied().collect::<Vec<_>>());
+    assert_eq!(v.iter().rev().copied().collect::<Vec<_>>(), ms.iter().rev().copied().collect::<Vec<_>>());
+    
+    // elements don't move so the handles still work
+    for (i, h) in handles.iter().enumerate() {
+        assert_eq!(i, ms.get_by_handle(*h).unwrap().1);
+    }
+    
+    ms.sort_by(|a, b| b.cmp(a));
+    v.sort_by(|a, b| b.cmp(a));
+    assert_eq!(v, ms.iter().copied().collect::<Vec<_>>());
+    assert_eq!(Some(&v[49]), ms.back());
+    
+    let mut one = StackStructure::<_, 3>::from_iter([1]);
+    one.sort();
+    assert_eq!(Some(&1), one.front());
+    let mut empty = StackStructure::<u8, 3>::new();
+    empty.sort();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_merge() {
+    use std::vec::Vec;
+    
+    let mut a = StackStructure::<(u8, char), 8>::from_iter([(1, 'a'), (3, 'a'), (5, 'a')]);
+    let b = StackStructure::<(u8, char), 4>::from_iter([(0, 'b'), (3, 'b'), (6, 'b'), (7, 'b')]);
+    a.merge_by(b, |x, y| x.0.cmp(&y.0)).unwrap();
+    assert_eq!(
+        [(0, 'b'), (1, 'a'), (3, 'a'), (3, 'b'), (5, 'a'), (6, 'b'), (7, 'b')][..],
+        a.iter().copied().collect::<Vec<_>>()[..]
+    );
+    
+    let c = StackStructure::<(u8, char), 2>::from_iter([(2, 'c'), (9, 'c')]);
+    let c = a.merge(c).unwrap_err().into_inner(); // only room for one
+    assert_eq!(7, a.len());
+    assert_eq!(2, c.len());
+    
+    let mut empty = StackStructure::<u8, 2>::new();
+    empty.merge(StackStructure::<u8, 2>::from_iter([4, 5])).unwrap();
+    assert_eq!([4, 5][..], empty.iter().copied().collect::<Vec<_>>()[..]);
+}
+
+#[test]
+fn test_append_prepend_split_off() {
+    use std::vec::Vec;
+    
+    let mut a = StackStructure::<u8, 6>::from_iter([3, 4]);
+    let mut b = StackStructure::<u8, 2>::from_iter([5, 6]);
+    let mut c = StackStructure::<u8, 9>::from_iter([1, 2]);
+    a.append(&mut b).unwrap();
+    a.prepend(&mut c).unwrap();
+    assert!(b.is_empty());
+    assert!(c.is_empty());
+    assert_eq!([1, 2, 3, 4, 5, 6][..], a.iter().copied().collect::<Vec<_>>()[..]);
+    
+    let mut d = StackStructure::<u8, 2>::from_iter([7]);
+    assert_eq!(Err(CapacityError::new(())), a.append(&mut d));
+    assert_eq!(Err(CapacityError::new(())), a.prepend(&mut d));
+    assert_eq!(6, a.len());
+    assert_eq!(1, d.len());
+    
+    assert!(a.split_off::<3>(2).is_err());
+    assert_eq!(6, a.len());
+    let tail = a.split_off::<4>(2).unwrap();
+    assert_eq!([1, 2][..], a.iter().copied().collect::<Vec<_>>()[..]);
+    assert_eq!([3, 4, 5, 6][..], tail.iter().copied().collect::<Vec<_>>()[..]);
+    let nothing = a.split_off::<0>(2).unwrap();
+    assert!(nothing.is_empty());
+    let all = a.split_off::<2>(0).unwrap();
+    assert!(a.is_empty());
+    assert_eq!(2, all.len());
+}
+
+#[test]
+fn test_retain_dedup_extract_if() {
+    use std::vec::Vec;
+    
+    let mut ms = StackStructure::<u8, 10>::from_iter([1, 1, 2, 3, 3, 3, 4, 5, 5, 6]);
+    ms.dedup();
+    assert_eq!([1, 2, 3, 4, 5, 6][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    assert_eq!(6, ms.len());
+    
+    ms.retain(|x| x % 3 != 0);
+    assert_eq!([1, 2, 4, 5][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    ms.retain_mut(|x| { *x *= 10; *x != 10 });
+    assert_eq!([20, 40, 50][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    
+    // the freed nodes went back to the free list
+    ms.extend([60, 61, 62, 70, 71, 80, 90]);
+    assert!(ms.is_full());
+    
+    ms.dedup_by_key(|x| *x / 10);
+    assert_eq!([20, 40, 50, 60, 70, 80, 90][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    
+    {
+        let mut extracted = ms.extract_if(|x| *x % 20 == 0);
+        assert_eq!(Some(20), extracted.next());
+        assert_eq!(Some(40), extracted.next());
+    } // lazy, 60 and 80 stay
+    assert_eq!([50, 60, 70, 80, 90][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    assert_eq!([60, 80][..], ms.extract_if(|x| *x % 20 == 0).collect::<Vec<_>>()[..]);
+    assert_eq!([50, 70, 90][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    assert_eq!(Some(&90), ms.back());
+    
+    ms.retain(|_| false);
+    assert!(ms.is_empty());
+    assert_eq!(None, ms.front());
+}
+
+#[test]
+fn test_drain_splice_insert_iter() {
+    use std::vec::Vec;
+    
+    let mut ms = StackStructure::<u8, 8>::from_iter([0, 1, 2, 3, 4, 5]);
+    let mut drain = ms.drain(1..5);
+    assert_eq!(4, drain.len());
+    assert_eq!(Some(1), drain.next());
+    assert_eq!(Some(4), drain.next_back());
+    drop(drain); // drops 2 and 3
+    assert_eq!([0, 5][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    assert_eq!(0, ms.drain(2..).count());
+    assert_eq!(0, ms.drain(0..0).count());
+    
+    ms.insert_iter(1, [1, 2, 3, 4]).unwrap();
+    ms.insert_iter(6, [6]).unwrap();
+    ms.insert_iter(0, None).unwrap();
+    assert_eq!([0, 1, 2, 3, 4, 5, 6][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    
+    // runs out of room after one item
+    let (rejected, rest) = ms.insert_iter(0, [10, 11, 12]).unwrap_err().into_inner();
+    assert_eq!(11, rejected);
+    assert_eq!([12][..], rest.collect::<Vec<_>>()[..]);
+    assert_eq!(Some(&10), ms.front());
+    assert!(ms.is_full());
+    
+    // replaces 2 with 3 so one more than fits
+    let mut splice = ms.splice(..=1, [20, 21, 22]);
+    assert_eq!(Some(10), splice.next());
+    let (rejected, mut rest) = splice.finish().unwrap_err().into_inner();
+    assert_eq!((22, None), (rejected, rest.next()));
+    assert_eq!([20, 21, 1, 2, 3, 4, 5, 6][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    
+    assert_eq!([1, 2, 3][..], ms.splice(2..5, [7]).collect::<Vec<_>>()[..]);
+    assert_eq!([20, 21, 7, 4, 5, 6][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    drop(ms.splice(6.., [8, 9]));
+    assert_eq!([20, 21, 7, 4, 5, 6, 8, 9][..], ms.iter().copied().collect::<Vec<_>>()[..]);
+    assert_eq!(Some(&9), ms.back());
+    assert_eq!(8, ms.drain(..).rev().count());
+    assert!(ms.is_empty());
+}
+
+#[test]
+fn test_compact() {
+    use std::vec::Vec;
+    
+    let mut ms = StackStructure::<u32, 10>::new();
+    for i in 0..10 {
+        ms.insert(i / 2, i as u32).unwrap();
+    }
+    ms.retain(|x| x % 3 != 0);
+    ms.push_front(100).unwrap();
+    let h = ms.push_with_handle(200).unwrap();
+    let before: Vec<u32> = ms.iter().copied().collect();
+    assert_eq!(before.iter().sum::<u32>(), ms.iter_unordered().sum::<u32>());
+    assert_eq!(before.len(), ms.iter_unordered().len());
+    
+    ms.compact();
+    assert_eq!(before, ms.iter().copied().collect::<Vec<_>>());
+    assert_eq!(before.iter().rev().copied().collect::<Vec<_>>(), ms.iter().rev().copied().collect::<Vec<_>>());
+    assert_eq!(before, ms.iter_unordered().copied().collect::<Vec<_>>()); // storage order is list order now
+    for i in 0..ms.len() {
+        assert_eq!(Some(i), ms.__locate(i));
+    }
+    assert_eq!(None, ms.get_by_handle(h)); // 200 moved
+    
+    for x in ms.iter_unordered_mut() {
+        *x += 1;
+    }
+    assert_eq!(before.iter().map(|x| x + 1).collect::<Vec<_>>(), ms.iter().copied().collect::<Vec<_>>());
+    
+    // the free nodes are all still usable
+    while ms.push(0).is_ok() {}
+    assert_eq!(10, ms.len());
+    ms.compact();
+    ms.clear();
+    ms.compact();
+    ms.extend(0..10);
+    assert_eq!((0..10).collect::<Vec<_>>(), ms.iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_std_traits() {
+    use std::collections::hash_map::DefaultHasher;
+    use core::hash::{Hash, Hasher};
+    fn hash_of<H: Hash + ?Sized>(h: &H) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        h.hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    // StackStructure, the layout in the main_memory doesn't matter
+    let a = StackStructure::<u8, 4>::from_iter([1, 2, 3]);
+    let mut b = StackStructure::<u8, 8>::default();
+    b.push(3).unwrap();
+    b.push_front(1).unwrap();
+    b.insert(1, 2).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a, [1, 2, 3]);
+    assert_eq!(a, &[1, 2, 3][..]);
+    assert!(a != [1, 2]);
+    assert_eq!(hash_of(&a), hash_of(&a.clone()));
+    assert!(a < StackStructure::<u8, 2>::from_iter([1, 3]));
+    assert_eq!(core::cmp::Ordering::Less, a.cmp(&StackStructure::from_iter([2])));
+    
+    let mut c = a.clone();
+    c[1] = 20;
+    assert_eq!(20, c[1]);
+    let mut sum = 0;
+    for x in &c {
+        sum += x;
+    }
+    assert_eq!(24, sum);
+    for x in &mut c {
+        *x += 1;
+    }
+    assert_eq!(c, [2, 21, 4]);
+    
+    // StackSimple
+    let s = StackSimple::<u8, 3>::from_array_of_same_capacity([1, 2, 3]);
+    let t = StackSimple::<u8, 10>::from_iter([1, 2, 3]);
+    assert_eq!(s, t);
+    assert_eq!(s, [1, 2, 3]);
+    assert_eq!(hash_of(&s), hash_of(&[1u8, 2, 3][..]));
+    assert!(s > StackSimple::<u8, 1>::from_iter([0]));
+    assert_eq!(StackSimple::<u8, 3>::new(), StackSimple::<u8, 3>::default());
+    let mut u = s.clone();
+    for x in &mut u {
+        *x *= 2;
+    }
+    assert_eq!(12, (&u).into_iter().sum::<u8>());
+    
+    // StackMap, the insertion order doesn't matter
+    let m = StackMap::<u8, &str, 4>::from_iter([(2, "b"), (1, "a")]);
+    let n = StackMap::<u8, &str, 6>::from_iter([(1, "a"), (2, "b")]);
+    assert_eq!(m, n);
+    assert_eq!(hash_of(&m), hash_of(&m.clone()));
+    assert_eq!("b", m[&2]);
+    assert!(m < StackMap::<u8, &str, 4>::from_iter([(1, "b")]));
+    let mut keys = 0;
+    for (k, _v) in &m {
+        keys += k;
+    }
+    assert_eq!(3, keys);
+    let mut o = m.clone();
+    for (_k, v) in &mut o {
+        *v = "z";
+    }
+    assert_eq!(o, StackMap::<u8, &str, 2>::from_iter([(1, "z"), (2, "z")]));
+    assert_eq!(StackMap::<u8, u8, 3>::new(), StackMap::<u8, u8, 3>::default());
+    assert!(StackMap::<u8, u8, 3>::new().is_empty() && !o.is_empty());
+}
+
+#[test]
+fn test_no_debug_bound() {
+    struct Opaque(u8); // no Debug
+    
+    let mut ms = StackStructure::<Opaque, 3>::new();
+    ms.push(Opaque(1)).unwrap();
+    ms.push_front(Opaque(0)).unwrap();
+    assert_eq!(1, ms.iter().nth(1).unwrap().0);
+    assert_eq!(Some(0), ms.pop_front().map(|o| o.0));
+    
+    let mut map = StackMap::<u8, Opaque, 3>::new();
+    map.insert(2, Opaque(2)).unwrap();
+    assert_eq!(2, map.get(&2).unwrap().0);
+    
+    let mut c = ms.cursor_front_mut();
+    c.insert_after(Opaque(5)).unwrap();
+    assert_eq!(2, ms.into_iter().count());
+    
+    // the Debug impls are still there when the element is Debug
+    assert_eq!("CapacityError", std::format!("{:?}", CapacityError::new(Opaque(0))));
+    assert!(std::format!("{:?}", StackStructure::<u8, 1>::from_iter([7])).contains('7'));
+}
+
+#[test]
+fn test_const_new() {
+    use std::sync::Mutex;
+    
+    static LIST: Mutex<StackStructure<u32, 4>> = Mutex::new(StackStructure::new());
+    static MAP: Mutex<StackMap<u8, u8, 2>> = Mutex::new(StackMap::new());
+    static SIMPLE: StackSimple<&str, 2> = StackSimple::from_array(["a", "b"]);
+    const EMPTY: StackSimple<u8, 2> = StackSimple::new();
+    
+    LIST.lock().unwrap().extend([1, 2, 3, 4]);
+    assert!(LIST.lock().unwrap().is_full());
+    assert_eq!(Some(4), LIST.lock().unwrap().pop_back());
+    MAP.lock().unwrap().insert(1, 1).unwrap();
+    assert_eq!(Some(&1), MAP.lock().unwrap().get(&1));
+    assert_eq!(SIMPLE, ["a", "b"]);
+    assert_eq!(0, EMPTY.len());
+}
+
+#[test]
+fn test_link_index_width() {
+    // the links are 1 byte each instead of an Option<usize>
+    assert!(core::mem::size_of::<StackStructure<u8, 64, u8>>() < core::mem::size_of::<StackStructure<u8, 64>>() / 2);
+    
+    let mut small = StackStructure::<u32, 255, u8>::new(); // 255 is the most that fits, u8::MAX is the sentinel
+    for i in 0..255 {
+        small.push(i).unwrap();
+    }
+    assert!(small.is_full());
+    assert_eq!(Err(CapacityError::new(255)), small.push(255));
+    small.retain(|x| x % 2 == 0);
+    small.sort_by(|a, b| b.cmp(a));
+    assert_eq!(Some(&254), small.front());
+    assert_eq!(Some(&0), small.back());
+    assert_eq!(Some(&252), small.get(1));
+    small.compact();
+    assert_eq!(Some(&2), small.iter().rev().nth(1));
+    
+    let mut medium = StackStructure::<u32, 300, u16>::from_iter(0..10);
+    let mut c = medium.cursor_back_mut();
+    c.insert_before(100).unwrap();
+    assert_eq!(Some(100), c.peek_prev().copied());
+    
+    // the lists with different link types work together
+    let mut wide = StackStructure::<u32, 20>::from_iter(20..22);
+    medium.append(&mut wide).unwrap();
+    assert_eq!(medium, StackStructure::<u32, 13, u8>::from_iter([0, 1, 2, 3, 4, 5, 6, 7, 8, 100, 9, 20, 21]));
+}
+
+#[test]
+fn test_node_storage_drops() {
+    use std::rc::Rc;
+    
+    // no Option discriminant per element, and the links are in their own array
+    assert_eq!(core::mem::size_of::<Node<u32>>(), 4 + 4 + 4);
+    
+    // each element is a clone of rc, so the strong count tells how many elements are alive
+    let rc = Rc::new(());
+    let mut ms = StackStructure::<Rc<()>, 8>::new();
+    for _ in 0..6 {
+        ms.push(rc.clone()).unwrap();
+    }
+    assert_eq!(7, Rc::strong_count(&rc));
+    drop(ms.delete(2).unwrap());
+    drop(ms.set(0, rc.clone()).unwrap());
+    ms.pop_front();
+    assert_eq!(5, Rc::strong_count(&rc));
+    ms.push_front(rc.clone()).unwrap(); // reuses a freed node
+    ms.compact();
+    assert_eq!(6, Rc::strong_count(&rc));
+    ms.truncate(3);
+    assert_eq!(4, Rc::strong_count(&rc));
+    drop(ms);
+    assert_eq!(1, Rc::strong_count(&rc));
+    
+    // the owning iterator drops what it didn't give out
+    let mut ms = StackStructure::<Rc<()>, 8>::from_iter((0..5).map(|_| rc.clone()));
+    ms.delete(1).unwrap();
+    let mut it = ms.into_iter();
+    it.next();
+    assert_eq!(4, Rc::strong_count(&rc));
+    drop(it);
+    assert_eq!(1, Rc::strong_count(&rc));
+    
+    let mut ms = StackStructure::<Rc<()>, 8>::from_iter((0..5).map(|_| rc.clone()));
+    ms.drain(1..3).next();
+    ms.retain(|_| false);
+    assert_eq!(1, Rc::strong_count(&rc));
+    
+    let map: StackMap<u8, Rc<()>, 4> = (0..4).map(|i| (i, rc.clone())).collect();
+    assert_eq!(5, Rc::strong_count(&rc));
+    drop(map);
+    assert_eq!(1, Rc::strong_count(&rc));
+}
+
+#[test]
+fn test_sort_free_list() {
+    let mut ms = StackStructure::<u32, 8>::from_iter(0..6);
+    let h = ms.push_with_handle(6).unwrap();
+    ms.delete(1).unwrap();
+    ms.delete(3).unwrap(); // the element 4
+    ms.remove_by_handle(h).unwrap();
+    assert_eq!(ms, [0, 2, 3, 5]);
+    
+    // without it the most recently freed node comes first
+    ms.sort_free_list();
+    assert_eq!(6, ms.fresh_nodes_start);
+    let a = ms.push_with_handle(10).unwrap();
+    let b = ms.push_with_handle(11).unwrap();
+    let c = ms.push_with_handle(12).unwrap();
+    assert_eq!((Some(1), Some(4), Some(6)), (ms.__node_of_handle(a), ms.__node_of_handle(b), ms.__node_of_handle(c)));
+    assert_eq!(ms, [0, 2, 3, 5, 10, 11, 12]);
+    assert_eq!(None, ms.get_by_handle(h));
+    
+    // compact moving elements into free nodes keeps the handles and the drops right
+    ms.pop_front();
+    ms.compact();
+    assert_eq!(None, ms.get_by_handle(a));
+    assert_eq!(ms, [2, 3, 5, 10, 11, 12]);
+    assert_eq!(ms.iter_unordered().copied().collect::<std::vec::Vec<_>>(), [2, 3, 5, 10, 11, 12]);
+}
+
+#[test]
+fn test_lowest_free_first() {
+    let mut ms = StackStructure::<u32, 8>::new_lowest_free_first();
+    ms.extend(0..8); // the element i is in node i
+    let node_of = |ms: &StackStructure<u32, 8>, h| ms.__node_of_handle(h).unwrap();
+    
+    ms.delete(5).unwrap();
+    ms.delete(2).unwrap();
+    ms.delete(4).unwrap(); // the element 6
+    let a = ms.push_with_handle(10).unwrap();
+    assert_eq!(2, node_of(&ms, a));
+    ms.delete(0).unwrap();
+    let b = ms.insert_with_handle(0, 11).unwrap();
+    let c = ms.push_with_handle(12).unwrap();
+    assert_eq!((0, 5), (node_of(&ms, b), node_of(&ms, c)));
+    ms.delete(4).unwrap(); // the element 7
+    ms.delete(1).unwrap(); // the element 1
+    let d = ms.push_with_handle(13).unwrap();
+    let e = ms.push_with_handle(14).unwrap();
+    let f = ms.push_with_handle(15).unwrap();
+    assert_eq!((1, 6, 7), (node_of(&ms, d), node_of(&ms, e), node_of(&ms, f)));
+    assert!(ms.is_full());
+    
+    // switching the mode on sorts the free_list that the default mode left behind
+    let mut ms = StackStructure::<u32, 8>::from_iter(0..8);
+    ms.delete(6).unwrap();
+    ms.delete(1).unwrap();
+    ms.delete(3).unwrap(); // the element 4
+    ms.set_lowest_free_first(true);
+    ms.delete(0).unwrap();
+    for expected_node_i in [0, 1, 4, 6] {
+        let h = ms.push_with_handle(20).unwrap();
+        assert_eq!(expected_node_i, node_of(&ms, h));
+    }
+    assert!(ms.clone().is_lowest_free_first());
+}
+
+#[test]
+fn test_interleaved_layout() {
+    use std::rc::Rc;
+    
+    // the same edits give the same list in both layouts
+    let mut split = StackStructure::<u32, 8>::from_iter(0..6);
+    let mut interleaved = StackStructure::<u32, 8, usize, Interleaved>::from_iter(0..6);
+    split.delete(2).unwrap();
+    interleaved.delete(2).unwrap();
+    split.push_front(10).unwrap();
+    interleaved.push_front(10).unwrap();
+    split.sort();
+    interleaved.sort();
+    split.dedup_by_key(|x| *x / 2);
+    interleaved.dedup_by_key(|x| *x / 2);
+    assert_eq!(split, interleaved);
+    assert_eq!(interleaved, [0, 3, 4, 10]);
+    assert!(!interleaved.is_compact());
+    interleaved.compact();
+    assert!(interleaved.is_compact());
+    assert_eq!(interleaved.iter_unordered().copied().collect::<std::vec::Vec<_>>(), [0, 3, 4, 10]);
+    interleaved.iter_unordered_mut().for_each(|x| *x += 1);
+    assert_eq!(interleaved, [1, 4, 5, 11]);
+    
+    // the handles and the drops don't depend on the layout
+    let rc = Rc::new(());
+    let mut ms = StackStructure::<Rc<()>, 4, u8, Interleaved>::new();
+    let h = ms.push_with_handle(rc.clone()).unwrap();
+    ms.push(rc.clone()).unwrap();
+    ms.push_front(rc.clone()).unwrap();
+    assert!(ms.get_by_handle(h).is_some());
+    ms.pop_back();
+    ms.compact(); // moves the element of h, so h goes stale
+    assert_eq!(None, ms.get_by_handle(h));
+    assert_eq!(3, Rc::strong_count(&rc));
+    drop(ms);
+    assert_eq!(1, Rc::strong_count(&rc));
+}
+
+#[test]
+fn test_contiguous_slice() {
+    let mut ms = StackStructure::<u32, 8>::from_iter(0..5);
+    assert_eq!(Some(&[0, 1, 2, 3, 4][..]), ms.as_slice());
+    ms.pop_front();
+    ms.push_front(10).unwrap(); // goes into the same node again
+    assert_eq!(Some(&[10, 1, 2, 3, 4][..]), ms.as_slice());
+    
+    ms.delete(2).unwrap();
+    ms.push(5).unwrap();
+    assert!(!ms.is_compact());
+    assert_eq!(None, ms.as_slice());
+    ms.compact();
+    assert_eq!(Some(&[10, 1, 3, 4, 5][..]), ms.as_slice());
+    
+    ms.as_mut_slice().unwrap().sort();
+    assert_eq!(ms, [1, 3, 4, 5, 10]);
+    ms.sort_by(|a, b| b.cmp(a));
+    assert_eq!(None, ms.as_mut_slice());
+    ms.compact();
+    assert_eq!(Some(&mut [10, 5, 4, 3, 1][..]), ms.as_mut_slice());
+    
+    ms.clear();
+    assert_eq!(Some(&[][..]), ms.as_slice());
+    assert_eq!(Some(&[][..]), StackStructure::<u32, 0>::new().as_slice());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde_test::{assert_tokens, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
+    
+    // list order, not storage order
+    let mut ms = StackStructure::<u8, 4>::from_iter([2, 3]);
+    ms.push_front(1).unwrap();
+    ms.delete(1).unwrap();
+    assert_tokens(&ms, &[Token::Seq{ len: Some(2) }, Token::U8(1), Token::U8(3), Token::SeqEnd]);
+    assert_tokens(&StackStructure::<u8, 0, u8>::new(), &[Token::Seq{ len: Some(0) }, Token::SeqEnd]);
+    assert_de_tokens_error::<StackStructure<u8, 2>>(
+        &[Token::Seq{ len: Some(3) }], // rejected from the size_hint before reading any element
+        "invalid length 3, expected a sequence of at most 2 elements",
+    );
+    assert_de_tokens_error::<StackStructure<u8, 2>>(
+        &[Token::Seq{ len: None }, Token::U8(1), Token::U8(2), Token::U8(3)],
+        "invalid length 3, expected a sequence of at most 2 elements",
+    );
+    
+    let ss = StackSimple::<&str, 3>::from_iter(["a", "b"]);
+    assert_tokens(&ss, &[Token::Seq{ len: Some(2) }, Token::BorrowedStr("a"), Token::BorrowedStr("b"), Token::SeqEnd]);
+    assert_de_tokens_error::<StackSimple<u8, 1>>(
+        &[Token::Seq{ len: None }, Token::U8(1), Token::U8(2)],
+        "invalid length 2, expected a sequence of at most 1 elements",
+    );
+    
+    let map = StackMap::<u8, char, 3>::from_iter([(2, 'b'), (1, 'a')]);
+    assert_tokens(&map, &[Token::Map{ len: Some(2) }, Token::U8(1), Token::Char('a'), Token::U8(2), Token::Char('b'), Token::MapEnd]);
+    // unsorted with a duplicate key, the last value wins and the duplicate doesn't take room
+    let map = StackMap::<u8, char, 3>::from_iter([(1, 'a'), (2, 'x'), (3, 'c')]);
+    assert_de_tokens(&map, &[
+        Token::Map{ len: Some(4) },
+        Token::U8(3), Token::Char('c'),
+        Token::U8(2), Token::Char('b'),
+        Token::U8(1), Token::Char('a'),
+        Token::U8(2), Token::Char('x'),
+        Token::MapEnd,
+    ]);
+    assert_de_tokens_error::<StackMap<u8, char, 1>>(
+        &[Token::Map{ len: Some(2) }, Token::U8(2), Token::Char('b'), Token::U8(1), Token::Char('a')],
+        "invalid length 2, expected a map of at most 1 entries",
+    );
+    assert_ser_tokens(&StackMap::<u8, u8, 0>::new(), &[Token::Map{ len: Some(0) }, Token::MapEnd]);
+}
+
+#[test]
+fn test_try_from_iter_try_extend_try_collect() {
+    let ms = StackStructure::<u8, 4>::try_from_iter(0..4).unwrap();
+    assert_eq!(ms, [0, 1, 2, 3]);
+    let e = StackStructure::<u8, 4>::try_from_iter(0..10).unwrap_err();
+    assert_eq!((4, &4), (e.accepted(), e.element()));
+    let (element, rest, ms) = e.into_parts();
+    assert_eq!(4, element);
+    assert!(rest.eq(5..10));
+    assert_eq!(ms, [0, 1, 2, 3]);
+    
+    let mut ss = StackSimple::<u8, 5>::from_iter([7]);
+    assert_eq!(Ok(2), ss.try_extend([8, 9]).map_err(|_| ()));
+    let e = ss.try_extend([10, 11, 12]).unwrap_err();
+    assert_eq!(2, e.accepted());
+    let (element, mut rest, ()) = e.into_parts();
+    assert_eq!((12, None), (element, rest.next()));
+    assert_eq!(ss, [7, 8, 9, 10, 11]);
+    
+    // the replaced values count as accepted and don't take room
+    let mut map = StackMap::<u8, char, 2>::new();
+    let e = map.try_extend([(1, 'a'), (1, 'b'), (2, 'c'), (3, 'd'), (4, 'e')]).unwrap_err();
+    assert_eq!((3, &(3, 'd')), (e.accepted(), e.element()));
+    assert!(map.iter().eq([(&1, &'b'), (&2, &'c')]));
+    
+    let ms: StackStructure<u8, 3, u8> = "abc".bytes().try_collect().unwrap();
+    assert_eq!(ms, *b"abc");
+    let e = "abcd".chars().try_collect::<StackSimple<char, 3>>().unwrap_err();
+    assert_eq!((&'d', &StackSimple::from_iter(['a', 'b', 'c'])), (e.element(), e.collection()));
+    assert!(core::iter::empty::<(u8, u8)>().try_collect::<StackMap<u8, u8, 0>>().is_ok());
+    assert_eq!(
+        "insufficient capacity: the collection was full after 0 elements",
+        std::format!("{}", [(0, 0)].into_iter().try_collect::<StackMap<u8, u8, 0>>().unwrap_err()),
+    );
+}
+
+#[test]
+fn test_simple_vec_api() {
+    use std::rc::Rc;
+    
+    let mut ss = StackSimple::<u8, 6>::from_iter([1, 2, 4]);
+    assert_eq!((6, false, false), (ss.capacity(), ss.is_full(), ss.is_empty()));
+    ss.insert(2, 3).unwrap();
+    ss.insert(0, 0).unwrap();
+    ss.insert(5, 5).unwrap();
+    assert!(ss.is_full());
+    assert_eq!(ss, [0, 1, 2, 3, 4, 5]);
+    assert_eq!(Err(InsertError::Full(CapacityError::new(9))), ss.insert(0, 9));
+    assert_eq!(Ok(0), ss.remove(0));
+    assert_eq!(Err(IndexOutOfBounds::new(9, 6, 5).into()), ss.insert(6, 9));
+    assert_eq!(Err(IndexOutOfBounds::new((), 5, 5)), ss.remove(5));
+    assert_eq!(Ok(1), ss.swap_remove(0));
+    assert_eq!(ss, [5, 2, 3, 4]);
+    assert_eq!(Ok(4), ss.swap_remove(3));
+    assert_eq!(Err(IndexOutOfBounds::new((), 3, 3)), ss.swap_remove(3));
+    
+    ss.extend_from_slice(&[6, 6, 7]).unwrap();
+    assert_eq!(Err(CapacityError::new(())), ss.extend_from_slice(&[8]));
+    ss.dedup();
+    assert_eq!(ss, [5, 2, 3, 6, 7]);
+    ss.retain(|x| x % 2 == 1);
+    assert_eq!(ss, [5, 3, 7]);
+    ss.resize(5, 1).unwrap();
+    assert_eq!(ss, [5, 3, 7, 1, 1]);
+    assert_eq!(Err(CapacityError::new(0)), ss.resize(7, 0));
+    ss.resize(2, 0).unwrap();
+    assert_eq!(ss, [5, 3]);
+    ss.dedup_by_key(|x| *x % 2);
+    assert_eq!(ss, [5]);
+    
+    let mut ss = StackSimple::<u8, 8>::from_iter(0..8);
+    assert!(ss.drain(2..5).eq([2, 3, 4]));
+    assert_eq!(ss, [0, 1, 5, 6, 7]);
+    let mut d = ss.drain(1..=3);
+    assert_eq!((Some(6), Some(1), 1), (d.next_back(), d.next(), d.len()));
+    drop(d);
+    assert_eq!(ss, [0, 7]);
+    let mut other = ss.split_off::<4>(1).unwrap();
+    assert_eq!((ss.len(), other.len()), (1, 1));
+    other.push(8).unwrap();
+    assert_eq!(Err(CapacityError::new(())), StackSimple::<u8, 3>::from_iter(0..3).split_off::<1>(1));
+    assert_eq!(other, [7, 8]);
+    other.truncate(9);
+    other.clear();
+    assert!(other.is_empty());
+    
+    // the drops, each element is a clone of rc
+    let rc = Rc::new(());
+    let mut ss = StackSimple::<Rc<()>, 8>::from_iter((0..8).map(|_| rc.clone()));
+    ss.truncate(6);
+    drop(ss.remove(0));
+    drop(ss.swap_remove(0));
+    assert_eq!(5, Rc::strong_count(&rc));
+    let mut n = 0;
+    ss.retain(|_| { n += 1; n % 2 == 0 });
+    assert_eq!(3, Rc::strong_count(&rc));
+    ss.resize(4, rc.clone()).unwrap();
+    ss.dedup_by(|_, _| true);
+    assert_eq!(2, Rc::strong_count(&rc));
+    ss.extend_from_slice(&[rc.clone(), rc.clone()]).unwrap();
+    ss.drain(..2).next();
+    assert_eq!(2, Rc::strong_count(&rc));
+    ss.clear();
+    assert_eq!(1, Rc::strong_count(&rc));
+}
+
+#[test]
+fn test_simple_slice_semantics() {
+    use std::panic::catch_unwind;
+    
+    let empty = StackSimple::<u8, 4>::new();
+    assert_eq!(&empty[..], &[] as &[u8]);
+    assert_eq!(&empty[0..0], &[] as &[u8]);
+    assert_eq!(&empty[0..], &[] as &[u8]);
+    assert_eq!(None, empty.get(0));
+    
+    let mut ss = StackSimple::<u8, 8>::from_iter([5, 1, 4, 2, 3]);
+    let len = ss.len();
+    assert_eq!(&ss[len..], &[] as &[u8]);
+    assert_eq!(&ss[2..2], &[] as &[u8]);
+    assert_eq!(&ss[1..=2], &[1, 4]);
+    assert_eq!(&ss[..=4], &[5, 1, 4, 2, 3]);
+    assert_eq!(ss.get(1..3), Some(&[1, 4][..]));
+    ss[1..3].copy_from_slice(&[9, 9]);
+    ss[0] = 0;
+    
+    // out of bounds is the same as the slice, including past len but inside the capacity
+    assert!(catch_unwind(|| StackSimple::<u8, 8>::from_iter([1, 2])[3..].len()).is_err());
+    assert!(catch_unwind(|| StackSimple::<u8, 8>::from_iter([1, 2])[..=2].len()).is_err());
+    assert!(catch_unwind(|| StackSimple::<u8, 8>::from_iter([1, 2])[2]).is_err());
+    #[allow(clippy::reversed_empty_ranges)]
+    let r = catch_unwind(|| StackSimple::<u8, 8>::from_iter([1, 2])[2..1].len());
+    assert!(r.is_err());
+    
+    // the slice methods
+    ss.sort();
+    assert_eq!(ss, [0, 2, 3, 9, 9]);
+    assert_eq!(Ok(2), ss.binary_search(&3));
+    assert_eq!(4, ss.windows(2).count());
+    assert_eq!(3, ss.chunks(2).count());
+    assert!(ss.contains(&2));
+    ss.reverse();
+    assert_eq!(Some(&0), ss.last());
+    
+    fn takes_slice<S: AsRef<[u8]> + core::borrow::Borrow<[u8]>>(s: S) -> usize { s.as_ref().len() + s.borrow().len() }
+    assert_eq!(10, takes_slice(ss));
+    let mut set = std::collections::HashSet::new();
+    set.insert(StackSimple::<u8, 3>::from_iter([1, 2]));
+    assert!(set.contains(&[1, 2][..]));
+}
+
+#[test]
+fn test_simple_drop() {
+    use std::rc::Rc;
+    use std::cell::Cell;
+    use std::string::{String, ToString};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    
+    // counts its drops, and panics in its drop if told to
+    struct D {
+        drops: Rc<Cell<usize>>,
+        panic_on_drop: bool,
+    }
+    impl Drop for D {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panic_on_drop {
+                core::panic!("drop of D");
+            }
+        }
+    }
+    let drops = Rc::new(Cell::new(0));
+    let d = |panic_on_drop: bool| D{ drops: drops.clone(), panic_on_drop };
+    
+    let ss = StackSimple::<D, 8>::from_iter((0..5).map(|_| d(false)));
+    drop(ss);
+    assert_eq!(5, drops.get());
+    drop(StackSimple::<D, 0>::new());
+    let strings = StackSimple::<String, 8>::from_iter((0..8).map(|i| i.to_string()));
+    drop(strings); // these leaked before StackSimple had a Drop impl
+    
+    // the items that were not yielded, from both ends
+    drops.set(0);
+    let mut it = StackSimple::<D, 8>::from_iter((0..6).map(|_| d(false))).into_iter();
+    drop(it.next());
+    drop(it.next_back());
+    assert_eq!(2, drops.get());
+    drop(it);
+    assert_eq!(6, drops.get());
+    
+    // a panic in the middle of FromIterator drops the items that were collected already
+    drops.set(0);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        StackSimple::<D, 8>::from_iter((0..5).map(|i| if i == 3 { core::panic!("in the iterator") } else { d(false) }))
+    }));
+    assert!(r.is_err());
+    assert_eq!(3, drops.get());
+    
+    // and in the middle of Extend
+    drops.set(0);
+    let mut ss = StackSimple::<D, 8>::from_iter([d(false)]);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        ss.extend((0..5).map(|i| if i == 2 { core::panic!("in the iterator") } else { d(false) }));
+    }));
+    assert!(r.is_err());
+    assert_eq!((0, 3), (drops.get(), ss.len()));
+    drop(ss);
+    assert_eq!(3, drops.get());
+    
+    // a panic in the drop of one element, the others are still dropped exactly once
+    drops.set(0);
+    let ss = StackSimple::<D, 8>::from_iter([d(false), d(true), d(false), d(false)]);
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(ss))).is_err());
+    assert_eq!(4, drops.get());
+    drops.set(0);
+    let mut it = StackSimple::<D, 8>::from_iter([d(false), d(false), d(true), d(false)]).into_iter();
+    drop(it.next());
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(it))).is_err());
+    assert_eq!(4, drops.get());
+    
+    // and in retain, the list stays valid
+    drops.set(0);
+    let mut ss = StackSimple::<D, 8>::from_iter([d(false), d(false), d(false), d(false)]);
+    let mut n = 0;
+    let r = catch_unwind(AssertUnwindSafe(|| ss.retain(|_| { n += 1; if n == 3 { core::panic!("in retain") } n == 2 })));
+    assert!(r.is_err());
+    assert_eq!((1, 3), (drops.get(), ss.len()));
+    drop(ss);
+    assert_eq!(4, drops.get());
+}
+
+#[test]
+fn test_simple_conversions() {
+    use std::rc::Rc;
+    use std::string::String;
+    
+    let ss = StackSimple::<u8, 5>::from([1, 2, 3]);
+    assert_eq!((ss.len(), ss.capacity()), (3, 5));
+    assert_eq!(ss, [1, 2, 3]);
+    assert_eq!(0, StackSimple::<u8, 0>::from([]).len());
+    
+    let ss = StackSimple::<u8, 3>::try_from(&[4, 5][..]).unwrap();
+    assert_eq!(ss, [4, 5]);
+    assert_eq!(Err(CapacityError::new(())), StackSimple::<u8, 1>::try_from(&[4, 5][..]));
+    
+    // only when full
+    let ss = ss.into_inner().unwrap_err();
+    let mut ss = ss.resize_capacity::<2>().unwrap();
+    assert_eq!(ss.capacity(), 2);
+    assert_eq!(Ok([4, 5]), <[u8; 2]>::try_from(ss.clone()));
+    ss.pop();
+    assert_eq!(Err(StackSimple::from([4])), <[u8; 2]>::try_from(ss));
+    let e = StackSimple::<u8, 3>::from([1, 2, 3]).resize_capacity::<2>().unwrap_err();
+    assert_eq!(e.into_inner(), [1, 2, 3]);
+    
+    // the elements are moved, not dropped or cloned
+    let rc = Rc::new(());
+    let ss = StackSimple::<Rc<()>, 4>::from([rc.clone(), rc.clone()]);
+    let ss = ss.resize_capacity::<8>().unwrap();
+    assert_eq!(3, Rc::strong_count(&rc));
+    let ss = ss.resize_capacity::<2>().unwrap();
+    let [a, b] = ss.into_inner().unwrap();
+    assert_eq!(3, Rc::strong_count(&rc));
+    drop((a, b));
+    assert_eq!(1, Rc::strong_count(&rc));
+    
+    let strings: [String; 2] = StackSimple::<String, 2>::from([String::from("a"), String::from("b")]).into_inner().unwrap();
+    assert_eq!(strings, ["a", "b"]);
+}
+
+#[test]
+fn test_stack_string() {
+    use core::fmt::Write;
+    use std::format;
+    
+    let mut s = StackString::<16>::new();
+    write!(s, "id={} t={:.1}", 42, 1.25).unwrap();
+    assert_eq!(s, "id=42 t=1.2");
+    assert_eq!((11, 5, 16), (s.len(), s.remaining_capacity(), s.capacity()));
+    assert!(s.starts_with("id=")); // str methods through deref
+    assert_eq!(Some(3), s.find('4'));
+    
+    // on overflow write! cuts on a char boundary and errors
+    let mut s = StackString::<8>::try_from("ab").unwrap();
+    let word = "déjà vu";
+    assert!(write!(s, "c{}", word).is_err());
+    assert_eq!(s, "abcdéj"); // the à doesn't fit in the last byte
+    assert_eq!(7, s.len());
+    assert_eq!(Err(CapacityError::new('à')), s.push('à'));
+    s.push('!').unwrap();
+    assert!(s.is_full());
+    assert_eq!(Err(CapacityError::new("x")), s.push_str("x"));
+    assert_eq!(Err(CapacityError::new("toolong")), StackString::<4>::try_from("toolong"));
+    
+    let mut s = StackString::<8>::try_from("añb").unwrap();
+    assert_eq!(Some('b'), s.pop());
+    assert_eq!(Some('ñ'), s.pop());
+    assert_eq!("a", s.as_str());
+    assert_eq!(None, StackString::<1>::new().pop());
+    let mut s = StackString::<8>::try_from("añb").unwrap();
+    s.truncate(9);
+    s.truncate(3);
+    assert_eq!(s, "añ");
+    assert!(std::panic::catch_unwind(|| StackString::<8>::try_from("añb").unwrap().truncate(2)).is_err());
+    let mut rest = StackString::<4>::new();
+    assert_eq!(Err(CapacityError::new("ef")), rest.push_str_truncating("abcdef"));
+    rest.make_ascii_uppercase(); // the &mut str methods through deref_mut
+    assert_eq!(rest, "ABCD");
+    rest.clear();
+    assert!(rest.is_empty());
+    
+    // the std traits go by the str
+    let a = StackString::<4>::try_from("ab").unwrap();
+    let b = StackString::<8>::try_from("ab").unwrap();
+    assert_eq!(a, b);
+    assert!(a < StackString::<2>::try_from("b").unwrap());
+    assert_eq!("ab", a);
+    assert_eq!("\"ab\"", format!("{:?}", a));
+    assert_eq!("  ab", format!("{:>4}", b));
+    assert_eq!(a, a.clone());
+    assert_eq!(StackString::<3>::default(), "");
+    let mut set = std::collections::HashSet::new();
+    set.insert(a);
+    assert!(set.contains("ab"));
+}
+
+#[test]
+fn test_byte_buffer() {
+    let mut ss = StackSimple::<u8, 4>::from([1]);
+    assert_eq!(3, ss.extend_from_slice_partial(&[2, 3, 4, 5]));
+    assert_eq!(0, ss.extend_from_slice_partial(&[6]));
+    let mut buf = [0u8; 3];
+    assert_eq!(3, ss.read_front(&mut buf));
+    assert_eq!(([1, 2, 3], &[4][..]), (buf, &ss[..]));
+    
+    let mut b = StackByteBuffer::<6>::new();
+    assert_eq!(4, b.extend_from_slice_partial(b"abcd"));
+    let mut buf = [0u8; 3];
+    assert_eq!(3, b.read_into(&mut buf));
+    assert_eq!((&buf, b"d".as_slice(), 5), (b"abc", b.unread(), b.remaining_capacity()));
+    // the consumed bytes make room only when it is needed
+    assert_eq!(2, b.extend_from_slice_partial(b"ef"));
+    assert_eq!(b"def", &b[..]);
+    assert_eq!(3, b.extend_from_slice_partial(b"ghijk"));
+    assert_eq!(b"defghi", &b[..]);
+    assert_eq!(0, b.extend_from_slice_partial(b"x"));
+    b.consume(2);
+    assert_eq!(b.len(), 4);
+    let inner = b.clone().into_inner();
+    assert_eq!(inner, *b"fghi");
+    b.consume(4);
+    assert!(b.is_empty());
+    assert_eq!(6, b.extend_from_slice_partial(b"012345")); // back at the front after everything was read
+    assert!(std::panic::catch_unwind(|| StackByteBuffer::<2>::from(StackSimple::from([1u8])).consume(2)).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_byte_buffer_std_io() {
+    use std::io::{Read, Write, BufRead};
+    
+    let mut ss = StackSimple::<u8, 8>::new();
+    write!(ss, "{}-{}", 12, 34).unwrap();
+    assert_eq!(std::io::ErrorKind::WriteZero, ss.write_all(b"5678").unwrap_err().kind());
+    assert_eq!(ss, *b"12-34567");
+    let mut s = std::string::String::new();
+    ss.read_to_string(&mut s).unwrap();
+    assert_eq!("12-34567", s);
+    
+    let mut b = StackByteBuffer::<8>::new();
+    b.write_all(b"line 1\n").unwrap();
+    let mut line = std::string::String::new();
+    b.read_line(&mut line).unwrap();
+    assert_eq!("line 1\n", line);
+    b.write_all(b"abc").unwrap();
+    assert_eq!(b"abc", b.fill_buf().unwrap());
+    BufRead::consume(&mut b, 1);
+    let mut rest = std::vec::Vec::new();
+    b.read_to_end(&mut rest).unwrap();
+    assert_eq!(b"bc", &rest[..]);
+}
+
+#[cfg(feature = "embedded-io")]
+#[test]
+fn test_byte_buffer_embedded_io() {
+    use embedded_io::{Read, Write, BufRead, Error, ErrorKind};
+    
+    let mut ss = StackSimple::<u8, 4>::new();
+    assert_eq!(Ok(4), ss.write(b"abcdef"));
+    assert_eq!(ErrorKind::WriteZero, ss.write(b"g").unwrap_err().kind());
+    assert_eq!(Ok(0), ss.write(b""));
+    let mut buf = [0u8; 3];
+    ss.read_exact(&mut buf).unwrap();
+    assert_eq!(b"abc", &buf);
+    
+    let mut b = StackByteBuffer::<4>::new();
+    b.write_all(b"wxyz").unwrap();
+    assert!(b.write_all(b"!").is_err());
+    assert_eq!(Ok(&b"wxyz"[..]), b.fill_buf());
+    BufRead::consume(&mut b, 2);
+    b.write_all(b"12").unwrap();
+    let mut buf = [0u8; 4];
+    assert_eq!(Ok(4), b.read(&mut buf));
+    assert_eq!(b"yz12", &buf);
+}
+
+#[test]
+fn test_stack_ring() {
+    let mut r = StackRing::<u8, 4>::new();
+    assert_eq!((None, None), (r.pop_front(), r.pop_back()));
+    r.push_back(2).unwrap();
+    r.push_back(3).unwrap();
+    r.push_front(1).unwrap(); // wraps around to data[3]
+    r.push_front(0).unwrap();
+    assert_eq!(Err(CapacityError::new(9)), r.push_back(9));
+    assert_eq!(Err(CapacityError::new(9)), r.push_front(9));
+    assert_eq!((&[0, 1][..], &[2, 3][..]), r.as_slices());
+    assert!(!r.is_contiguous());
+    assert_eq!((0, 3, Some(&3)), (r[0], r[3], r.back()));
+    assert_eq!(None, r.get(4));
+    r[1] = 10;
+    *r.front_mut().unwrap() += 5;
+    r.iter_mut().for_each(|x| *x *= 2);
+    assert_eq!(r, [10, 20, 4, 6]);
+    assert_eq!(&mut [10, 20, 4, 6], r.make_contiguous());
+    assert_eq!((&[10, 20, 4, 6][..], &[][..]), r.as_slices());
+    assert_eq!((Some(10), Some(6)), (r.pop_front(), r.pop_back()));
+    assert_eq!(r.iter().copied().collect::<std::vec::Vec<_>>(), [20, 4]);
+    assert!(std::panic::catch_unwind(|| StackRing::<u8, 4>::from_iter([1, 2])[2]).is_err());
+    
+    // a sample buffer that keeps the last 3 values
+    let mut samples = StackRing::<u32, 3>::new();
+    let evicted: std::vec::Vec<_> = (1..=5).filter_map(|x| samples.push_back_overwriting(x)).collect();
+    assert_eq!(evicted, [1, 2]);
+    assert_eq!(samples, [3, 4, 5]);
+    assert_eq!(Some(5), samples.push_front_overwriting(2));
+    assert_eq!(samples, [2, 3, 4]);
+    assert_eq!(Some(7), StackRing::<u32, 0>::new().push_back_overwriting(7));
+    
+    // the elements are dropped once, wherever they are in the data
+    let rc = std::rc::Rc::new(());
+    let mut r = StackRing::<_, 3>::new();
+    for _ in 0..5 {
+        r.push_back_overwriting(rc.clone());
+    }
+    r.pop_front();
+    r.push_front(rc.clone()).unwrap();
+    assert_eq!(4, std::rc::Rc::strong_count(&rc));
+    let mut it = r.clone().into_iter();
+    assert_eq!(7, std::rc::Rc::strong_count(&rc));
+    it.next_back();
+    assert_eq!(2, it.len());
+    drop(it);
+    drop(r);
+    assert_eq!(1, std::rc::Rc::strong_count(&rc));
+    
+    let r: StackRing<u8, 5> = (0..4).rev().collect();
+    assert_eq!(std::format!("{:?}", r), "[3, 2, 1, 0]");
+    assert_eq!(r, StackRing::<u8, 8>::from_iter([3, 2, 1, 0]));
+}
+
+#[test]
+fn test_stack_ring_drop_panic() {
+    use std::rc::Rc;
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    
+    // counts its drops, and panics in its drop if told to
+    struct D {
+        drops: Rc<Cell<usize>>,
+        panic_on_drop: bool,
+    }
+    impl Drop for D {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panic_on_drop {
+                core::panic!("drop of D");
+            }
+        }
+    }
+    let drops = Rc::new(Cell::new(0));
+    let d = |panic_on_drop: bool| D{ drops: drops.clone(), panic_on_drop };
+    // wrapped around, the front part is data[2..4] and the back part data[0..2]. the panic is in the front part
+    let wrapped = || {
+        let mut r = StackRing::<D, 4>::new();
+        r.push_back(d(false)).unwrap();
+        r.push_back(d(false)).unwrap();
+        r.push_front(d(false)).unwrap();
+        r.push_front(d(true)).unwrap();
+        assert!(!r.is_contiguous());
+        r
+    };
+    
+    // in Drop the back part is still dropped
+    let r = wrapped();
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(r))).is_err());
+    assert_eq!(4, drops.get());
+    
+    // and in clear, the ring is empty after
+    drops.set(0);
+    let mut r = wrapped();
+    assert!(catch_unwind(AssertUnwindSafe(|| r.clear())).is_err());
+    assert_eq!((4, 0), (drops.get(), r.len()));
+    drop(r);
+    assert_eq!(4, drops.get());
+}
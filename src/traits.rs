@@ -0,0 +1,97 @@
+use super::*;
+use core::hash::{Hash, Hasher};
+use core::cmp::Ordering;
+
+// the std traits for StackStructure. all of them go by the elements in list order, never by how the nodes sit in the main_memory.
+// the comparisons work between lists of different capacities and link index types, and against slices and arrays.
+
+impl<T: Clone, const N: usize, Idx: LinkIndex, L: Layout> Clone for StackStructure<T, N, Idx, L> {
+    fn clone(&self) -> Self {
+        let mut ms: Self = self.iter().cloned().collect(); // compact, so the free_list is empty and sorted in any mode
+        ms.lowest_free_first = self.lowest_free_first;
+        ms
+    }
+}
+
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> Default for StackStructure<T, N, Idx, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize, Idx: LinkIndex, Idx2: LinkIndex, L: Layout, L2: Layout> PartialEq<StackStructure<U, M, Idx2, L2>> for StackStructure<T, N, Idx, L> {
+    fn eq(&self, other: &StackStructure<U, M, Idx2, L2>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+impl<T: PartialEq<U>, U, const N: usize, Idx: LinkIndex, L: Layout> PartialEq<[U]> for StackStructure<T, N, Idx, L> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+impl<'a, T: PartialEq<U>, U, const N: usize, Idx: LinkIndex, L: Layout> PartialEq<&'a [U]> for StackStructure<T, N, Idx, L> {
+    fn eq(&self, other: &&'a [U]) -> bool {
+        *self == **other
+    }
+}
+impl<T: PartialEq<U>, U, const N: usize, const M: usize, Idx: LinkIndex, L: Layout> PartialEq<[U; M]> for StackStructure<T, N, Idx, L> {
+    fn eq(&self, other: &[U; M]) -> bool {
+        *self == other[..]
+    }
+}
+impl<T: Eq, const N: usize, Idx: LinkIndex, L: Layout> Eq for StackStructure<T, N, Idx, L> {}
+
+impl<T: PartialOrd, const N: usize, const M: usize, Idx: LinkIndex, Idx2: LinkIndex, L: Layout, L2: Layout> PartialOrd<StackStructure<T, M, Idx2, L2>> for StackStructure<T, N, Idx, L> {
+    fn partial_cmp(&self, other: &StackStructure<T, M, Idx2, L2>) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+impl<T: Ord, const N: usize, Idx: LinkIndex, L: Layout> Ord for StackStructure<T, N, Idx, L> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash, const N: usize, Idx: LinkIndex, L: Layout> Hash for StackStructure<T, N, Idx, L> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+// these walk the list, see get and get_mut
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> core::ops::Index<usize> for StackStructure<T, N, Idx, L> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            None => core::panic!("index out of bounds: the len is {} but the index is {}", self.len, index),
+            Some(element) => element,
+        }
+    }
+}
+impl<T, const N: usize, Idx: LinkIndex, L: Layout> core::ops::IndexMut<usize> for StackStructure<T, N, Idx, L> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len;
+        match self.get_mut(index) {
+            None => core::panic!("index out of bounds: the len is {} but the index is {}", len, index),
+            Some(element) => element,
+        }
+    }
+}
+
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> IntoIterator for &'a StackStructure<T, N, Idx, L> {
+    type Item = &'a T;
+    type IntoIter = StackStructureIteratorRef<'a, T, N, Idx, L>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const N: usize, Idx: LinkIndex, L: Layout> IntoIterator for &'a mut StackStructure<T, N, Idx, L> {
+    type Item = &'a mut T;
+    type IntoIter = StackStructureIteratorRefMut<'a, T, N, Idx, L>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}